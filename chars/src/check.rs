@@ -0,0 +1,350 @@
+//! Lint text files for characters that are hard to spot in an editor
+//! or a code review: invisible and format characters, bidirectional
+//! controls (as used in "Trojan Source" attacks), non-breaking spaces,
+//! and non-ASCII lookalikes that sneak into otherwise-ASCII lines.
+
+use std::char;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::unicode::confusables;
+
+/// The reason a character was reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A zero-width or other invisible formatting character.
+    Invisible,
+    /// A byte order mark anywhere but at the very start of the file.
+    ByteOrderMark,
+    /// A bidirectional embedding, override, isolate or mark.
+    BidiControl,
+    /// A space that looks like U+0020 but doesn't break lines.
+    NonBreakingSpace,
+    /// A non-ASCII character that is confusable with ASCII, in a line
+    /// that is otherwise written in ASCII.
+    Lookalike,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let desc = match *self {
+            Kind::Invisible => "invisible character",
+            Kind::ByteOrderMark => "byte order mark after start of file",
+            Kind::BidiControl => "bidirectional control character",
+            Kind::NonBreakingSpace => "non-breaking space",
+            Kind::Lookalike => "non-ASCII character in ASCII text",
+        };
+        write!(f, "{}", desc)
+    }
+}
+
+/// A single suspicious character found in a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub c: char,
+    pub kind: Kind,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}:{}: {}: U+{:04X}",
+            self.line, self.column, self.kind, self.c as u32
+        )?;
+        if let Some(name) = unicode_names2::name(self.c) {
+            write!(f, " {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Characters that are known to be fine, either everywhere or only in
+/// files under a certain path.
+///
+/// An allowlist file has one character per line, given as the
+/// character itself, its code point (`U+00A0` or `0xA0`) or its exact
+/// name (`no-break space`); names aren't searched, so that `arrow`
+/// doesn't allow every arrow. Lines starting with `#` are comments, and a `[path/prefix]` line restricts all following
+/// entries to files whose path starts with that prefix:
+///
+///```text
+///# Allowed in all files:
+///U+00A0
+///[docs/]
+///RIGHTWARDS ARROW
+///```
+#[derive(Debug, Default, Clone)]
+pub struct Allowlist {
+    entries: Vec<(Option<String>, BTreeSet<char>)>,
+}
+
+impl Allowlist {
+    pub fn new() -> Allowlist {
+        Default::default()
+    }
+
+    pub fn from_file(path: &Path) -> io::Result<Allowlist> {
+        Allowlist::parse(&fs::read_to_string(path)?).map_err(|msg| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), msg),
+            )
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Allowlist, String> {
+        let mut allowlist = Allowlist::new();
+        let mut prefix: Option<String> = None;
+        let mut chars = BTreeSet::new();
+        for (lineno, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.len() > 2 && line.starts_with('[') && line.ends_with(']') {
+                allowlist.entries.push((prefix, chars));
+                prefix = Some(line[1..line.len() - 1].to_owned());
+                chars = BTreeSet::new();
+                continue;
+            }
+            match allowlist_entry(line) {
+                Some(c) => chars.insert(c),
+                None => return Err(format!("line {}: No character “{}”.", lineno + 1, line)),
+            };
+        }
+        allowlist.entries.push((prefix, chars));
+        Ok(allowlist)
+    }
+
+    pub fn allows(&self, path: &str, c: char) -> bool {
+        self.entries.iter().any(|(prefix, chars)| {
+            let in_scope = match prefix {
+                Some(prefix) => path.starts_with(prefix.as_str()),
+                None => true,
+            };
+            in_scope && chars.contains(&c)
+        })
+    }
+}
+
+/// Resolves an allowlist entry to the one character it stands for.
+fn allowlist_entry(entry: &str) -> Option<char> {
+    let mut chars = entry.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c);
+    }
+    if let Some(hex) = entry
+        .strip_prefix("U+")
+        .or_else(|| entry.strip_prefix("0x"))
+    {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    }
+    unicode_names2::character(entry)
+}
+
+#[test]
+fn allowlist_scopes_by_path() {
+    let allowlist = Allowlist::parse("# comment\nU+00A0\n\n[docs/]\nzero width joiner\n").unwrap();
+    assert!(allowlist.allows("src/main.rs", '\u{a0}'));
+    assert!(allowlist.allows("docs/index.md", '\u{a0}'));
+    assert!(allowlist.allows("docs/index.md", '\u{200d}'));
+    assert!(!allowlist.allows("src/main.rs", '\u{200d}'));
+    assert!(Allowlist::parse("no such character name at all").is_err());
+    // Names must be exact, not searches:
+    assert!(Allowlist::parse("arrow").is_err());
+    let allowlist = Allowlist::parse("rightwards arrow\n0x2190\n↓").unwrap();
+    assert!(allowlist.allows("a", '→'));
+    assert!(allowlist.allows("a", '←'));
+    assert!(allowlist.allows("a", '↓'));
+    assert!(!allowlist.allows("a", '↑'));
+}
+
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{061c}' | '\u{200e}' | '\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00ad}'
+            | '\u{034f}'
+            | '\u{115f}'
+            | '\u{1160}'
+            | '\u{17b4}'
+            | '\u{17b5}'
+            | '\u{180e}'
+            | '\u{200b}'..='\u{200d}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{3164}'
+            | '\u{ffa0}'
+            | '\u{e0000}'..='\u{e007f}'
+    )
+}
+
+fn is_non_breaking_space(c: char) -> bool {
+    matches!(c, '\u{00a0}' | '\u{2007}' | '\u{202f}')
+}
+
+/// Rough approximation of the characters that may surround a zero
+/// width joiner in an emoji ZWJ sequence.
+fn is_emoji_component(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2300}'..='\u{23ff}'
+            | '\u{2600}'..='\u{27bf}'
+            | '\u{2b00}'..='\u{2bff}'
+            | '\u{fe0f}'
+            | '\u{1f000}'..='\u{1faff}'
+    )
+}
+
+/// Whether `c` is confusable with ASCII text, according to UTS #39.
+fn has_ascii_prototype(c: char) -> bool {
+    !c.is_ascii() && matches!(confusables::prototype(c), Some(proto) if proto.is_ascii())
+}
+
+/// Whether every character in the line is ASCII, leaving aside the
+/// lookalikes themselves and the characters reported for other reasons.
+fn otherwise_ascii(chars: &[char]) -> bool {
+    chars.iter().all(|&c| {
+        c.is_ascii()
+            || c == '\u{feff}'
+            || has_ascii_prototype(c)
+            || is_bidi_control(c)
+            || is_invisible(c)
+            || is_non_breaking_space(c)
+    })
+}
+
+fn classify_line(lineno: usize, line: &str, start_of_file: bool, findings: &mut Vec<Finding>) {
+    let chars: Vec<char> = line.chars().collect();
+    let ascii_line = otherwise_ascii(&chars);
+    for (i, &c) in chars.iter().enumerate() {
+        let kind = if c == '\u{feff}' {
+            if start_of_file && i == 0 {
+                continue;
+            }
+            Kind::ByteOrderMark
+        } else if is_bidi_control(c) {
+            Kind::BidiControl
+        } else if c == '\u{200d}'
+            && i > 0
+            && is_emoji_component(chars[i - 1])
+            && matches!(chars.get(i + 1), Some(&n) if is_emoji_component(n))
+        {
+            continue;
+        } else if is_invisible(c) {
+            Kind::Invisible
+        } else if is_non_breaking_space(c) {
+            Kind::NonBreakingSpace
+        } else if ascii_line && has_ascii_prototype(c) {
+            Kind::Lookalike
+        } else {
+            continue;
+        };
+        findings.push(Finding {
+            line: lineno,
+            column: i + 1,
+            c,
+            kind,
+        });
+    }
+}
+
+/// Returns every suspicious character in `text`, in the order they
+/// occur.
+pub fn check_str(text: &str) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (i, line) in text.split('\n').enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        classify_line(i + 1, line, i == 0, &mut findings);
+    }
+    findings
+}
+
+/// Checks the file at `path`, leaving out any characters that the
+/// allowlist permits for it.
+pub fn check_file(path: &Path, allowlist: &Allowlist) -> io::Result<Vec<Finding>> {
+    let text = fs::read_to_string(path)?;
+    let name = path.to_string_lossy();
+    Ok(check_str(&text)
+        .into_iter()
+        .filter(|finding| !allowlist.allows(&name, finding.c))
+        .collect())
+}
+
+#[test]
+fn check_finds_trojan_source() {
+    let findings = check_str(
+        "if access_level != \"user\u{202e} \u{2066}// Check if admin\u{2069} \u{2066}\" {\n",
+    );
+    let kinds: Vec<(usize, Kind)> = findings.iter().map(|f| (f.column, f.kind)).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (25, Kind::BidiControl),
+            (27, Kind::BidiControl),
+            (45, Kind::BidiControl),
+            (47, Kind::BidiControl),
+        ]
+    );
+}
+
+#[test]
+fn check_finds_invisibles() {
+    assert!(check_str("\u{feff}fn main() {}\n").is_empty());
+    assert_eq!(
+        check_str("fn main() {}\n\u{feff}")
+            .first()
+            .map(|f| (f.line, f.kind)),
+        Some((2, Kind::ByteOrderMark))
+    );
+    assert_eq!(
+        check_str("let a\u{200b}b = 1;")[0],
+        Finding {
+            line: 1,
+            column: 6,
+            c: '\u{200b}',
+            kind: Kind::Invisible
+        }
+    );
+    assert_eq!(check_str("let x =\u{a0}1;")[0].kind, Kind::NonBreakingSpace);
+}
+
+#[test]
+fn check_allows_emoji_zwj_sequences() {
+    assert!(check_str("family: 👨\u{200d}👩\u{200d}👧")
+        .iter()
+        .all(|f| f.kind != Kind::Invisible));
+    assert_eq!(check_str("a\u{200d}b")[0].kind, Kind::Invisible);
+}
+
+#[test]
+fn check_finds_lookalikes() {
+    let findings = check_str("let p\u{0430}ssw\u{043e}rd = 1;\n// Привет, мир\n");
+    let positions: Vec<(usize, usize, Kind)> = findings
+        .iter()
+        .map(|f| (f.line, f.column, f.kind))
+        .collect();
+    assert_eq!(
+        positions,
+        vec![(1, 6, Kind::Lookalike), (1, 10, Kind::Lookalike)]
+    );
+}
+
+#[test]
+fn check_ignores_non_ascii_text() {
+    assert!(check_str("let name = \"café\";\n").is_empty());
+    assert!(check_str("let price = \"10€\";\n").is_empty());
+    assert!(check_str("// a—b\n").is_empty());
+    // A lookalike in a line that isn't otherwise ASCII is just text:
+    assert!(check_str("// pаssword, café\n").is_empty());
+}
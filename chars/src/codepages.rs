@@ -0,0 +1,82 @@
+//! Legacy single-byte code pages, which assign characters to the
+//! bytes above (and sometimes below) ASCII.
+
+include!(concat!(env!("OUT_DIR"), "/codepages/codepages.rs"));
+
+/// Compares code page names the way people write them: "iso8859-15",
+/// "ISO_8859_15" and "ISO-8859-15" are the same.
+pub fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Returns the code page called `name` or one of its aliases,
+/// ignoring case and punctuation.
+pub fn lookup(name: &str) -> Option<&'static CodePage> {
+    let name = normalize(name);
+    CODE_PAGES.iter().find(|cp| {
+        normalize(cp.name) == name || cp.aliases.iter().any(|alias| normalize(alias) == name)
+    })
+}
+
+/// Returns the byte that encodes `c` in `code_page`, if it has one.
+pub fn encode(code_page: &CodePage, c: char) -> Option<u8> {
+    code_page
+        .chars
+        .iter()
+        .position(|&ch| ch == Some(c))
+        .map(|byte| byte as u8)
+}
+
+/// Returns the code pages that contain `c`, with the byte that encodes
+/// it in each.
+pub fn positions(c: char) -> Vec<(&'static str, u8)> {
+    CODE_PAGES
+        .iter()
+        .filter_map(|cp| encode(cp, c).map(|byte| (cp.name, byte)))
+        .collect()
+}
+
+/// Decodes a byte given as `codepage:byte`, like `cp1252:0x80` or
+/// `ebcdic:193`.
+pub fn decode_spec(spec: &str) -> Option<char> {
+    let (name, byte) = spec.split_once(':')?;
+    let code_page = lookup(name)?;
+    let byte = match byte.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16).ok()?,
+        None => byte.parse::<u8>().ok()?,
+    };
+    code_page.chars[byte as usize]
+}
+
+#[test]
+fn code_page_positions() {
+    let cp1252 = lookup("cp1252").unwrap();
+    assert_eq!(cp1252.chars[0x80], Some('€'));
+    assert_eq!(cp1252.chars[0x81], None);
+    assert_eq!(encode(cp1252, '€'), Some(0x80));
+    assert_eq!(encode(cp1252, 'A'), Some(0x41));
+    assert_eq!(encode(lookup("CP437").unwrap(), '░'), Some(0xb0));
+    assert_eq!(lookup("iso8859_15").unwrap().name, "ISO-8859-15");
+    assert_eq!(lookup("latin1").unwrap().name, "ISO-8859-1");
+    assert!(lookup("CP999").is_none());
+    let euro = positions('€');
+    assert!(euro.contains(&("CP1252", 0x80)));
+    assert!(euro.contains(&("ISO-8859-15", 0xa4)));
+    assert!(euro.contains(&("MacRoman", 0xdb)));
+    assert!(!euro.iter().any(|&(name, _)| name == "ISO-8859-1"));
+    assert!(positions('A').contains(&("CP037", 0xc1)));
+}
+
+#[test]
+fn decoding_bytes() {
+    assert_eq!(decode_spec("cp1252:0x80"), Some('€'));
+    assert_eq!(decode_spec("ebcdic:0xC1"), Some('A'));
+    assert_eq!(decode_spec("koi8-r:0xC1"), Some('а'));
+    assert_eq!(decode_spec("ebcdic:193"), Some('A'));
+    assert_eq!(decode_spec("cp1252:0x81"), None);
+    assert_eq!(decode_spec("cp1252:0x100"), None);
+    assert_eq!(decode_spec("nonsense:0x80"), None);
+}
@@ -7,11 +7,275 @@ use byteorder::{BigEndian, ByteOrder};
 use unicode_width::UnicodeWidthChar;
 
 use super::ascii;
+use super::codepages;
+use super::encodings;
+use super::glyph_names;
+use super::groff;
+use super::keysyms;
+use super::latex;
+use super::mojibake;
+use super::typing;
+use super::unicode::{
+    aliases, casing, confusables, hangul, names_list, normalization, properties, surrogates, unihan,
+};
 
 pub fn describe(c: char) {
     println!("{}\n", Describable::from(c));
 }
 
+/// Describes a surrogate code point, which isn't a character on its
+/// own but shows up in broken UTF-16 and in WTF-8.
+pub fn describe_surrogate(cp: u32) {
+    let role = if surrogates::HIGH_SURROGATES.contains(&cp) {
+        "high (leading)"
+    } else {
+        "low (trailing)"
+    };
+    println!(
+        "U+{:04X}, 0x{:04X}, \\0{:o}, UTF-16BE: {:04x}",
+        cp, cp, cp, cp
+    );
+    println!(
+        "Surrogate code point: the {} half of a UTF-16 surrogate pair, not a character on its own",
+        role
+    );
+    println!("Block: {}", surrogates::block_name(cp));
+    match surrogates::combined_range(cp) {
+        Some((first, last)) if surrogates::HIGH_SURROGATES.contains(&cp) => println!(
+            "Pairs with a low surrogate into U+{:04X}..U+{:04X}",
+            u32::from(first),
+            u32::from(last)
+        ),
+        Some((first, last)) => println!(
+            "Pairs with a high surrogate into every 1024th character from U+{:04X} to U+{:04X}",
+            u32::from(first),
+            u32::from(last)
+        ),
+        None => {}
+    }
+    let bytes = ByteRepresentation::Utf8(surrogates::encode_generalized_utf8(cp));
+    println!("WTF-8: {}, CESU-8: {}", bytes, bytes);
+    println!("Not valid in UTF-8 or well-formed UTF-16 when unpaired\n");
+}
+
+/// Describes how a high and a low surrogate combine into a
+/// supplementary character.
+pub fn describe_surrogate_pair(high: u32, low: u32) {
+    if let Some(c) = surrogates::combine(high, low) {
+        let mut buf = [0; 4];
+        println!(
+            "U+{:04X} U+{:04X} is the surrogate pair for U+{:04X} {}",
+            high,
+            low,
+            u32::from(c),
+            name_or_codepoint(c)
+        );
+        println!(
+            "WTF-8: {} (same as UTF-8), CESU-8: {}\n",
+            ByteRepresentation::from(c.encode_utf8(&mut buf).bytes()),
+            ByteRepresentation::Utf8(surrogates::encode_cesu8(c))
+        );
+    }
+}
+
+/// Prints the confusable skeleton of each string, and whether the
+/// strings are confusable with each other.
+pub fn describe_skeletons(strings: &[String]) {
+    let skeletons: Vec<String> = strings.iter().map(|s| confusables::skeleton(s)).collect();
+    for (s, skeleton) in strings.iter().zip(skeletons.iter()) {
+        let scalars: Vec<String> = skeleton
+            .chars()
+            .map(|c| format!("U+{:04X}", c as u32))
+            .collect();
+        println!("{}: {} ({})", s, skeleton, scalars.join(" "));
+    }
+    if skeletons.len() > 1 {
+        if skeletons.iter().all(|s| *s == skeletons[0]) {
+            println!("\nAll strings are confusable.");
+        } else {
+            println!("\nThe strings are not all confusable.");
+        }
+    }
+}
+
+/// Prints the scalars of `s` in all four normalization forms, and the
+/// normalization properties of each character in it.
+pub fn describe_normalizations(s: &str) {
+    print!("Input: {}", s);
+    describe_scalars(s);
+    let forms = [
+        ("NFC", normalization::nfc(s)),
+        ("NFD", normalization::nfd(s)),
+        ("NFKC", normalization::nfkc(s)),
+        ("NFKD", normalization::nfkd(s)),
+    ];
+    for (form, normalized) in forms.iter() {
+        if normalized == s {
+            print!("\n{}: {} (same as input)", form, normalized);
+        } else {
+            print!("\n{}: {} (DIFFERS from input)", form, normalized);
+        }
+        describe_scalars(normalized);
+    }
+
+    println!("\nNormalization properties:");
+    for c in s.chars() {
+        let (nfc_qc, nfkc_qc) = normalization::quick_check(c);
+        print!(
+            "  U+{:04X} {}: NFC_QC={:?}, NFKC_QC={:?}",
+            c as u32,
+            name_or_codepoint(c),
+            nfc_qc,
+            nfkc_qc
+        );
+        match normalization::composition_exclusion(c) {
+            Some(normalization::Exclusion::Listed) => print!(", Composition_Exclusion"),
+            Some(normalization::Exclusion::Singleton) => {
+                print!(", Full_Composition_Exclusion (singleton decomposition)")
+            }
+            Some(normalization::Exclusion::NonStarter) => {
+                print!(", Full_Composition_Exclusion (non-starter decomposition)")
+            }
+            None => {}
+        }
+        println!();
+    }
+}
+
+/// Prints `s` converted into simplified or traditional characters
+/// (depending on `field`), along with the characters that changed and
+/// those that could have been converted differently.
+pub fn describe_conversion(s: &str, field: &str) {
+    println!("Input: {}", s);
+    println!("Output: {}", unihan::convert(s, field));
+    for c in s.chars() {
+        let (converted, others) = unihan::convert_char(c, field);
+        if converted != c {
+            print!("  {} → {} {}", c, converted, name_or_codepoint(converted));
+        } else if !others.is_empty() {
+            print!("  {} kept", c);
+        } else {
+            continue;
+        }
+        if !others.is_empty() {
+            let others: Vec<String> = others.iter().map(char::to_string).collect();
+            print!(" (could also be {})", others.join(" "));
+        }
+        println!();
+    }
+}
+
+/// Prints the most plausible originals of mojibake `s`, explaining
+/// how each of its characters got garbled.
+pub fn describe_mojibake(s: &str) {
+    println!("Input: {}", escape_controls(s));
+    let repairs = mojibake::repairs(s);
+    let best = match repairs.first() {
+        Some(best) => best,
+        None => {
+            println!("No common misreading explains this text.");
+            return;
+        }
+    };
+    let misreading = best.misreading;
+    let times = match best.depth {
+        1 => String::new(),
+        2 => " twice".to_string(),
+        n => format!(" {} times", n),
+    };
+    println!(
+        "Most plausible original: {} ({} read as {}{})",
+        best.text(),
+        misreading.written_as(),
+        misreading.read_as(),
+        times
+    );
+    for piece in &best.pieces {
+        let c = match piece.original {
+            Some(c) => c,
+            None if misreading.read_as() == "UTF-8" => {
+                println!(
+                    "  U+FFFD: a byte of {} that isn't valid UTF-8 was lost, so this can't be recovered",
+                    misreading.written_as(),
+                );
+                continue;
+            }
+            None => {
+                println!(
+                    "  U+FFFD: {} {} is damaged, so this can't be recovered",
+                    misreading.written_as(),
+                    ByteRepresentation::Utf8(piece.bytes.clone())
+                );
+                continue;
+            }
+        };
+        let mut garbled = c.to_string();
+        let mut steps: Vec<String> = vec![];
+        for _ in 0..best.depth {
+            let bytes = misreading.encode(&garbled);
+            garbled = misreading.misread(&bytes);
+            steps.push(format!(
+                "{} {} read as {} is {}",
+                misreading.written_as(),
+                ByteRepresentation::Utf8(bytes),
+                misreading.read_as(),
+                escape_controls(&garbled)
+            ));
+        }
+        if garbled != c.to_string() {
+            println!(
+                "  {} {}: {}",
+                c,
+                name_or_codepoint(c),
+                steps.join(", whose ")
+            );
+        }
+    }
+    for repair in &repairs[1..] {
+        println!(
+            "Could also be: {} ({} read as {})",
+            repair.text(),
+            repair.misreading.written_as(),
+            repair.misreading.read_as()
+        );
+    }
+}
+
+/// Escapes the control characters in `s` (but not line breaks or
+/// tabs), which mojibake is full of.
+fn escape_controls(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_control() && !c.is_whitespace() {
+                c.escape_debug().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+fn describe_scalars(s: &str) {
+    println!();
+    for c in s.chars() {
+        let mut buf = [0; 4];
+        let utf8 = ByteRepresentation::from(c.encode_utf8(&mut buf).bytes());
+        println!(
+            "  U+{:04X} {}, UTF-8: {}",
+            c as u32,
+            name_or_codepoint(c),
+            utf8
+        );
+    }
+}
+
+fn name_or_codepoint(c: char) -> String {
+    match unicode_names2::name(c) {
+        Some(name) => name.to_string(),
+        None => format!("U+{:04X}", c as u32),
+    }
+}
+
 struct Describable {
     c: char,
 }
@@ -20,12 +284,73 @@ impl fmt::Display for Describable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         let cp: Codepoint = self.c.into();
         cp.fmt(f)?;
+        fmt_classification(f, self.c)?;
         let printable: Printable = self.c.into();
         write!(f, "\n{}", printable)?;
         let unicode_name = unicode_names2::name(self.c);
         if let Some(n) = unicode_name.clone() {
             write!(f, "\nUnicode name: {}", n)?;
         }
+        if let Some(old_name) = aliases::unicode_1_name(self.c) {
+            write!(f, "\nUnicode 1.0 name: {}", old_name)?;
+        }
+        let mut alias_types: Vec<&str> = vec![];
+        for &(_, _, alias_type) in aliases::aliases(self.c) {
+            if !alias_types.contains(&alias_type) {
+                alias_types.push(alias_type);
+            }
+        }
+        for alias_type in alias_types {
+            let names: Vec<&str> = aliases::aliases(self.c)
+                .iter()
+                .filter(|&&(_, _, t)| t == alias_type)
+                .map(|&(_, alias, _)| alias)
+                .collect();
+            write!(
+                f,
+                "\n{}: {}",
+                aliases::describe_alias_type(alias_type),
+                names.join(", ")
+            )?;
+        }
+        if let Some(readings) = unihan::readings(self.c) {
+            fmt_unihan_readings(f, readings)?;
+        }
+        for rs in unihan::radical_strokes(self.c) {
+            fmt_radical_stroke(f, rs)?;
+        }
+        for (field, variant) in unihan::variants(self.c) {
+            write!(
+                f,
+                "\n{}: {} {}",
+                unihan::describe_variant_field(field),
+                variant,
+                name_or_codepoint(variant)
+            )?;
+        }
+        if let Some(annotations) = names_list::annotations(self.c) {
+            if !annotations.aliases.is_empty() {
+                write!(
+                    f,
+                    "\nInformative aliases: {}",
+                    annotations.aliases.join("; ")
+                )?;
+            }
+            for note in annotations.notes {
+                write!(f, "\nChart note: {}", note)?;
+            }
+            if !annotations.see_also.is_empty() {
+                let see_also: Vec<String> = annotations
+                    .see_also
+                    .iter()
+                    .map(|&c| format!("{} ({})", c, name_or_codepoint(c)))
+                    .collect();
+                write!(f, "\nSee also: {}", see_also.join(", "))?;
+            }
+        }
+        if let Some(subheader) = names_list::subheader(self.c) {
+            write!(f, "\nListed in the code charts under: {}", subheader)?;
+        }
         if let Some(ascii) = ascii::additional_names(self.c) {
             let mut synonyms: Vec<&str> = vec![];
             let mut xmls: Option<&str> = None;
@@ -59,10 +384,235 @@ impl fmt::Display for Describable {
                 write!(f, "\nNote: {}", n)?;
             }
         }
+        if let Some(proto) = confusables::prototype(self.c) {
+            let names: Vec<String> = proto.chars().map(name_or_codepoint).collect();
+            write!(f, "\nConfusable with: {} ({})", names.join(", "), proto)?;
+        }
+        let skeleton = confusables::skeleton(&self.c.to_string());
+        if skeleton != normalization::nfd(&self.c.to_string()) {
+            write!(f, "\nSkeleton: {}", skeleton)?;
+        }
+        fmt_hangul(f, self.c)?;
+        let commands = latex::commands(self.c);
+        if !commands.is_empty() {
+            write!(f, "\nLaTeX: {}", commands.join(", "))?;
+        }
+        fmt_code_pages(f, self.c)?;
+        if !self.c.is_ascii() {
+            fmt_cjk_encodings(f, self.c)?;
+        }
+        write!(f, "\ngroff: {}", groff::escape(self.c))?;
+        fmt_keysyms(f, self.c)?;
+        write!(
+            f,
+            "\nPostScript glyph name: {}",
+            glyph_names::glyph_names(self.c).join(", ")
+        )?;
+        let digraphs: Vec<&str> = typing::digraphs(self.c).collect();
+        if !digraphs.is_empty() {
+            write!(f, "\nVim digraph: {}", digraphs.join(", "))?;
+        }
+        if !self.c.is_ascii() {
+            fmt_typing(f, self.c)?;
+        }
+        let lookalikes = confusables::lookalikes(self.c);
+        if !lookalikes.is_empty() {
+            let lookalikes: Vec<String> = lookalikes.iter().map(char::to_string).collect();
+            write!(f, "\nLookalikes: {}", lookalikes.join(" "))?;
+        }
         Ok(())
     }
 }
 
+fn fmt_classification(f: &mut fmt::Formatter<'_>, c: char) -> Result<(), fmt::Error> {
+    match properties::plane(c) {
+        (number, name, Some(abbreviation)) => {
+            write!(f, "\nPlane {}: {} ({})", number, name, abbreviation)?
+        }
+        (number, name, None) => write!(f, "\nPlane {}: {}", number, name)?,
+    }
+    if properties::is_noncharacter(c) {
+        write!(
+            f,
+            "\nNoncharacter: permanently reserved for internal use, \
+             should not be interchanged"
+        )?;
+    } else if properties::is_private_use(c) {
+        write!(
+            f,
+            "\nPrivate use: has no meaning except by private agreement \
+             between sender and receiver"
+        )?;
+    } else if properties::general_category(c) == "Cn" {
+        write!(
+            f,
+            "\nUnassigned: reserved for future versions of Unicode, \
+             should not be used yet"
+        )?;
+    }
+    if properties::is_default_ignorable(c) {
+        write!(
+            f,
+            "\nDefault ignorable: invisible when not supported, \
+             instead of showing a missing glyph"
+        )?;
+    }
+    Ok(())
+}
+
+fn fmt_unihan_readings(
+    f: &mut fmt::Formatter<'_>,
+    readings: &unihan::Readings,
+) -> Result<(), fmt::Error> {
+    let hangul = unihan::hangul_readings(readings.hangul).join(" ");
+    for (label, value) in &[
+        ("Definition", readings.definition),
+        ("Mandarin", readings.mandarin),
+        ("Cantonese", readings.cantonese),
+        ("Japanese On", readings.japanese_on),
+        ("Japanese Kun", readings.japanese_kun),
+        ("Korean", hangul.as_str()),
+    ] {
+        if !value.is_empty() {
+            write!(f, "\n{}: {}", label, value)?;
+        }
+    }
+    Ok(())
+}
+
+fn fmt_radical_stroke(
+    f: &mut fmt::Formatter<'_>,
+    rs: &unihan::RadicalStroke,
+) -> Result<(), fmt::Error> {
+    write!(f, "\nRadical: {}{}", rs.radical, rs.form.marker())?;
+    if let Some(radical) = unihan::radical(rs.radical, rs.form) {
+        match radical.radical {
+            Some(r) => write!(
+                f,
+                " {} {} (as in {})",
+                r,
+                name_or_codepoint(r),
+                radical.ideograph
+            )?,
+            None => write!(f, " (as in {})", radical.ideograph)?,
+        }
+        let supplement: Vec<String> = radical
+            .radical
+            .map(unihan::supplement_forms)
+            .unwrap_or_default()
+            .iter()
+            .filter(|&&c| Some(c) != radical.radical)
+            .map(|&c| format!("{} {}", c, name_or_codepoint(c)))
+            .collect();
+        if !supplement.is_empty() {
+            write!(f, ", also written {}", supplement.join(", "))?;
+        }
+    }
+    write!(f, "; residual strokes: {}", rs.strokes)
+}
+
+fn jamo_with_short_name(jamo: char) -> String {
+    match hangul::short_name(jamo) {
+        Some("") => format!("{} (silent)", jamo),
+        Some(short_name) => format!("{} ({})", jamo, short_name),
+        None => jamo.to_string(),
+    }
+}
+
+fn fmt_hangul(f: &mut fmt::Formatter<'_>, c: char) -> Result<(), fmt::Error> {
+    let syllable_type = match hangul::syllable_type(c) {
+        Some(syllable_type) => syllable_type,
+        None => return Ok(()),
+    };
+    write!(
+        f,
+        "\nHangul syllable type: {} ({})",
+        syllable_type.short_name(),
+        syllable_type.description()
+    )?;
+    if let Some((l, v, t)) = hangul::decompose(c) {
+        let mut jamo = vec![jamo_with_short_name(l), jamo_with_short_name(v)];
+        jamo.extend(t.map(jamo_with_short_name));
+        write!(f, "\nComposed of jamo: {}", jamo.join(" + "))?;
+    } else if let Some(short_name) = hangul::short_name(c) {
+        write!(f, "\nJamo short name: {:?}", short_name)?;
+    }
+    Ok(())
+}
+
+/// Lists the legacy code pages that encode `c`, grouped by byte. For
+/// ASCII characters, only the code pages that put them elsewhere (like
+/// EBCDIC) are interesting.
+fn fmt_code_pages(f: &mut fmt::Formatter<'_>, c: char) -> Result<(), fmt::Error> {
+    let mut bytes: Vec<(u8, Vec<&str>)> = vec![];
+    for (name, byte) in codepages::positions(c) {
+        if c.is_ascii() && u32::from(byte) == c as u32 {
+            continue;
+        }
+        match bytes.iter_mut().find(|(b, _)| *b == byte) {
+            Some((_, names)) => names.push(name),
+            None => bytes.push((byte, vec![name])),
+        }
+    }
+    if !bytes.is_empty() {
+        let positions: Vec<String> = bytes
+            .iter()
+            .map(|(byte, names)| format!("0x{:02X} in {}", byte, names.join(", ")))
+            .collect();
+        write!(f, "\nIn code pages: {}", positions.join("; "))?;
+    }
+    Ok(())
+}
+
+/// Lists the bytes that encode `c` in the multi-byte East Asian
+/// encodings that have it.
+fn fmt_cjk_encodings(f: &mut fmt::Formatter<'_>, c: char) -> Result<(), fmt::Error> {
+    let sequences: Vec<String> = encodings::ENCODINGS
+        .iter()
+        .filter_map(|encoding| {
+            let bytes = encoding.encode(c)?;
+            Some(format!(
+                "{} in {}",
+                ByteRepresentation::Utf8(bytes),
+                encoding.name()
+            ))
+        })
+        .collect();
+    if !sequences.is_empty() {
+        write!(f, "\nIn CJK encodings: {}", sequences.join("; "))?;
+    }
+    Ok(())
+}
+
+fn fmt_keysyms(f: &mut fmt::Formatter<'_>, c: char) -> Result<(), fmt::Error> {
+    let mut keysyms: Vec<String> = keysyms::keysyms(c)
+        .map(|(name, value)| format!("{} ({:#06x})", name, value))
+        .collect();
+    if keysyms.is_empty() {
+        let (name, value) = keysyms::unicode_keysym(c);
+        keysyms.push(format!("{} ({:#x})", name, value));
+    }
+    write!(f, "\nX11 keysym: {}", keysyms.join(", "))
+}
+
+fn fmt_typing(f: &mut fmt::Formatter<'_>, c: char) -> Result<(), fmt::Error> {
+    write!(f, "\nHow to type it:")?;
+    let compose = typing::compose_sequences(c);
+    if !compose.is_empty() {
+        let sequences: Vec<String> = compose.iter().map(|s| format!("Compose {}", s)).collect();
+        write!(f, "\n  X11 Compose key: {}", sequences.join(" or "))?;
+    }
+    write!(f, "\n  GTK: {}", typing::gtk_sequence(c))?;
+    write!(f, "\n  Windows: {}", typing::windows_alt_x(c))?;
+    for code in typing::windows_alt_codes(c) {
+        write!(f, " or {}", code)?;
+    }
+    if let Some(sequence) = typing::macos_sequence(c) {
+        write!(f, "\n  macOS (US layout): {}", sequence)?;
+    }
+    Ok(())
+}
+
 impl convert::From<char> for Describable {
     fn from(c: char) -> Describable {
         Describable { c }
@@ -122,13 +672,131 @@ impl fmt::Display for Printable {
                     caseflipped.push(c);
                 }
                 write!(f, "\nLower case. Upcases to {}", caseflipped)?;
+            } else if self.c.to_uppercase().ne(Some(self.c))
+                && self.c.to_lowercase().ne(Some(self.c))
+            {
+                let upper: String = self.c.to_uppercase().collect();
+                let lower: String = self.c.to_lowercase().collect();
+                write!(
+                    f,
+                    "\nTitle case. Upcases to {}, downcases to {}",
+                    upper, lower
+                )?;
+            }
+            if let Some(title) = casing::titlecase(self.c) {
+                write!(f, "\nTitle cases to {}", title)?;
             }
+            self.fmt_case_folding(f)?;
 
             // If we have quotable text, print that too:
             if quote.len() > 1 {
                 write!(f, "\nQuotes as {}", quote)?;
             }
         }
+        let east_asian_width = properties::east_asian_width(self.c);
+        let line_break = properties::line_break(self.c);
+        write!(
+            f,
+            "\nEast Asian Width: {} ({}), line break class: {} ({})",
+            properties::east_asian_width_name(east_asian_width),
+            east_asian_width,
+            properties::line_break_name(line_break),
+            line_break
+        )?;
+        if east_asian_width == "A" {
+            write!(
+                f,
+                "\nAmbiguous width: narrow in most contexts, but wide in East Asian \
+                 (CJK) fonts and terminals"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Printable {
+    fn fmt_case_folding(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        if let Some((simple, full)) = casing::case_folding(self.c) {
+            if full == simple.to_string() {
+                write!(f, "\nCase folds to {}", full)?;
+            } else {
+                write!(
+                    f,
+                    "\nCase folds to {} (simple case folding: {})",
+                    full, simple
+                )?;
+            }
+        }
+        if let Some(turkic) = casing::turkic_case_folding(self.c) {
+            write!(f, "\nCase folds to {} in Turkish and Azeri", turkic)?;
+        }
+        // Turkish and Azeri share their rules, so merge the languages of
+        // otherwise identical special casings:
+        let mut specials: Vec<(Vec<&str>, Vec<&str>, String)> = vec![];
+        let lower = self.c.to_lowercase().collect::<String>();
+        let upper = self.c.to_uppercase().collect::<String>();
+        let title = casing::titlecase(self.c).map_or_else(|| upper.clone(), str::to_string);
+        for special in casing::conditional_casings(self.c) {
+            let mut mappings: Vec<String> = vec![];
+            for (kind, mapping, default) in &[
+                ("lower", special.lower, &lower),
+                ("title", special.title, &title),
+                ("upper", special.upper, &upper),
+            ] {
+                if *mapping == default.as_str() {
+                    continue;
+                }
+                if mapping.is_empty() {
+                    mappings.push(format!("{} case removes it", kind));
+                } else {
+                    mappings.push(format!("{} case {}", kind, mapping));
+                }
+            }
+            if mappings.is_empty() {
+                continue;
+            }
+            let mappings = mappings.join(", ");
+            let (languages, contexts): (Vec<&str>, Vec<&str>) = special
+                .conditions
+                .iter()
+                .partition(|c| casing::is_language(c));
+            let languages: Vec<&str> = languages
+                .into_iter()
+                .map(casing::describe_condition)
+                .collect();
+            let contexts: Vec<&str> = contexts
+                .into_iter()
+                .map(casing::describe_condition)
+                .collect();
+            match specials
+                .iter_mut()
+                .find(|(_, ctx, m)| *ctx == contexts && *m == mappings)
+            {
+                Some((langs, _, _)) => langs.extend(languages),
+                None => specials.push((languages, contexts, mappings)),
+            }
+        }
+        for (languages, contexts, mappings) in specials {
+            let mut conditions: Vec<String> = vec![];
+            if !languages.is_empty() {
+                conditions.push(languages.join(" and "));
+            }
+            conditions.extend(contexts.into_iter().map(String::from));
+            write!(
+                f,
+                "\nSpecial casing ({}): {}",
+                conditions.join(", "),
+                mappings
+            )?;
+        }
+        let equivalents: Vec<String> = casing::case_class(self.c)
+            .into_iter()
+            .filter(|&c| c != self.c)
+            .map(|c| format!("{} ({})", c, name_or_codepoint(c)))
+            .collect();
+        if !equivalents.is_empty() {
+            write!(f, "\nCase equivalents: {}", equivalents.join(", "))?;
+        }
         Ok(())
     }
 }
@@ -202,7 +870,7 @@ impl fmt::Display for Codepoint {
     }
 }
 
-enum ByteRepresentation {
+pub(crate) enum ByteRepresentation {
     Utf8(Vec<u8>),
     Utf16Be(Vec<u8>),
 }
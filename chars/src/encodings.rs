@@ -0,0 +1,477 @@
+//! Multi-byte East Asian legacy encodings, following the encoders and
+//! decoders of the WHATWG Encoding Standard.
+
+use std::char;
+
+use super::codepages::normalize;
+
+include!(concat!(env!("OUT_DIR"), "/codepages/encodings.rs"));
+
+/// The encodings that characters are shown in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    ShiftJis,
+    EucJp,
+    Iso2022Jp,
+    Gb18030,
+    Big5,
+    EucKr,
+}
+
+pub const ENCODINGS: &[Encoding] = &[
+    Encoding::ShiftJis,
+    Encoding::EucJp,
+    Encoding::Iso2022Jp,
+    Encoding::Gb18030,
+    Encoding::Big5,
+    Encoding::EucKr,
+];
+
+/// The pointer in the GB18030 index where the four-byte sequences for
+/// characters outside the BMP start.
+const GB18030_SUPPLEMENTARY: u32 = 189_000;
+
+const ESC: u8 = 0x1b;
+
+/// The pointers of `c` in `index`, lowest first.
+fn pointers(index: &[(char, u16)], c: char) -> impl Iterator<Item = u32> + '_ {
+    let start = index.partition_point(|&(ch, _)| ch < c);
+    index[start..]
+        .iter()
+        .take_while(move |&&(ch, _)| ch == c)
+        .map(|&(_, pointer)| u32::from(pointer))
+}
+
+fn pointer(index: &[(char, u16)], c: char) -> Option<u32> {
+    pointers(index, c).next()
+}
+
+fn code_point(index: &[(char, u16)], pointer: u32) -> Option<char> {
+    index
+        .iter()
+        .find(|&&(_, p)| u32::from(p) == pointer)
+        .map(|&(c, _)| c)
+}
+
+/// The pointer of `c` in the Big5 index, for encoding. Pointers below
+/// lead byte 0xA1 (Hong Kong extensions) aren't used, and characters
+/// that are in the index twice are encoded with their last pointer.
+fn big5_pointer(c: char) -> Option<u32> {
+    let pointers = pointers(BIG5, c).filter(|&p| p >= (0xa1 - 0x81) * 157);
+    match c {
+        '\u{2550}' | '\u{255e}' | '\u{2561}' | '\u{256a}' | '\u{5341}' | '\u{5345}' => {
+            pointers.last()
+        }
+        _ => pointers.min(),
+    }
+}
+
+fn gb18030_ranges_pointer(c: char) -> Option<u32> {
+    if c == '\u{e7c7}' {
+        return Some(7457);
+    }
+    if c as u32 >= 0x10000 {
+        return Some(GB18030_SUPPLEMENTARY + c as u32 - 0x10000);
+    }
+    let i = GB18030_RANGES.partition_point(|&(_, start)| start <= c);
+    let &(pointer, start) = GB18030_RANGES.get(i.checked_sub(1)?)?;
+    Some(pointer + c as u32 - start as u32)
+}
+
+fn gb18030_ranges_code_point(pointer: u32) -> Option<char> {
+    // Four-byte sequences between U+FFFF and U+10000, and beyond
+    // U+10FFFF, don't stand for anything:
+    if (pointer > 39419 && pointer < GB18030_SUPPLEMENTARY) || pointer > 1_237_575 {
+        return None;
+    }
+    if pointer == 7457 {
+        return Some('\u{e7c7}');
+    }
+    if pointer >= GB18030_SUPPLEMENTARY {
+        return char::from_u32(0x10000 + pointer - GB18030_SUPPLEMENTARY);
+    }
+    let i = GB18030_RANGES.partition_point(|&(p, _)| p <= pointer);
+    let &(p, start) = GB18030_RANGES.get(i.checked_sub(1)?)?;
+    char::from_u32(start as u32 + pointer - p)
+}
+
+impl Encoding {
+    /// The name the encoding is known by.
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::ShiftJis => "Shift_JIS",
+            Encoding::EucJp => "EUC-JP",
+            Encoding::Iso2022Jp => "ISO-2022-JP",
+            Encoding::Gb18030 => "GB18030",
+            Encoding::Big5 => "Big5",
+            Encoding::EucKr => "EUC-KR",
+        }
+    }
+
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Encoding::ShiftJis => &["sjis", "cp932", "windows-31j"],
+            Encoding::EucJp => &["ujis"],
+            Encoding::Iso2022Jp => &["jis"],
+            Encoding::Gb18030 => &["gbk", "gb2312", "cp936"],
+            Encoding::Big5 => &["cp950"],
+            Encoding::EucKr => &["cp949", "uhc", "ks_c_5601-1987"],
+        }
+    }
+
+    /// Returns the encoding called `name` or one of its aliases,
+    /// ignoring case and punctuation.
+    pub fn from_name(name: &str) -> Option<Encoding> {
+        let name = normalize(name);
+        ENCODINGS.iter().cloned().find(|encoding| {
+            normalize(encoding.name()) == name
+                || encoding
+                    .aliases()
+                    .iter()
+                    .any(|alias| normalize(alias) == name)
+        })
+    }
+
+    /// Returns the bytes that encode `c`, if the encoding has it.
+    pub fn encode(self, c: char) -> Option<Vec<u8>> {
+        if c.is_ascii() {
+            return Some(vec![c as u8]);
+        }
+        let halfwidth_katakana = ('\u{ff61}'..='\u{ff9f}').contains(&c);
+        match self {
+            Encoding::ShiftJis => {
+                if halfwidth_katakana {
+                    return Some(vec![(c as u32 - 0xff61 + 0xa1) as u8]);
+                }
+                // Pointers 8272 to 8835 duplicate other characters and
+                // aren't used for encoding:
+                let pointer =
+                    pointers(JIS0208, c).find(|pointer| !(8272..=8835).contains(pointer))?;
+                let (lead, trail) = (pointer / 188, pointer % 188);
+                let lead_offset = if lead < 0x1f { 0x81 } else { 0xc1 };
+                let trail_offset = if trail < 0x3f { 0x40 } else { 0x41 };
+                Some(vec![
+                    (lead + lead_offset) as u8,
+                    (trail + trail_offset) as u8,
+                ])
+            }
+            Encoding::EucJp => {
+                if halfwidth_katakana {
+                    return Some(vec![0x8e, (c as u32 - 0xff61 + 0xa1) as u8]);
+                }
+                let pointer = pointer(JIS0208, c)?;
+                Some(vec![
+                    (pointer / 94 + 0xa1) as u8,
+                    (pointer % 94 + 0xa1) as u8,
+                ])
+            }
+            Encoding::Iso2022Jp => {
+                let pointer = pointer(JIS0208, c)?;
+                Some(vec![
+                    ESC,
+                    b'$',
+                    b'B',
+                    (pointer / 94 + 0x21) as u8,
+                    (pointer % 94 + 0x21) as u8,
+                    ESC,
+                    b'(',
+                    b'B',
+                ])
+            }
+            Encoding::Gb18030 => {
+                if let Some(pointer) = pointer(GB18030, c) {
+                    let (lead, trail) = (pointer / 190, pointer % 190);
+                    let trail_offset = if trail < 0x3f { 0x40 } else { 0x41 };
+                    return Some(vec![(lead + 0x81) as u8, (trail + trail_offset) as u8]);
+                }
+                let pointer = gb18030_ranges_pointer(c)?;
+                Some(vec![
+                    (pointer / 12600 + 0x81) as u8,
+                    (pointer % 12600 / 1260 + 0x30) as u8,
+                    (pointer % 1260 / 10 + 0x81) as u8,
+                    (pointer % 10 + 0x30) as u8,
+                ])
+            }
+            Encoding::Big5 => {
+                let pointer = big5_pointer(c)?;
+                let (lead, trail) = (pointer / 157, pointer % 157);
+                let trail_offset = if trail < 0x3f { 0x40 } else { 0x62 };
+                Some(vec![(lead + 0x81) as u8, (trail + trail_offset) as u8])
+            }
+            Encoding::EucKr => {
+                let pointer = pointer(EUC_KR, c)?;
+                Some(vec![
+                    (pointer / 190 + 0x81) as u8,
+                    (pointer % 190 + 0x41) as u8,
+                ])
+            }
+        }
+    }
+
+    /// Decodes `bytes`, if they are a valid sequence in the encoding.
+    pub fn decode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            Encoding::Iso2022Jp => decode_iso_2022_jp(bytes),
+            _ => {
+                let mut decoded = String::new();
+                let mut rest = bytes;
+                while !rest.is_empty() {
+                    let (c, len) = self.decode_one(rest)?;
+                    decoded.push(c);
+                    rest = &rest[len..];
+                }
+                Some(decoded)
+            }
+        }
+    }
+
+    /// Decodes the first character of `bytes`, returning it with the
+    /// number of bytes it took.
+    fn decode_one(self, bytes: &[u8]) -> Option<(char, usize)> {
+        let lead = *bytes.first()?;
+        if lead < 0x80 {
+            return Some((lead as char, 1));
+        }
+        let trail = u32::from(*bytes.get(1).unwrap_or(&0));
+        let lead = u32::from(lead);
+        match self {
+            Encoding::ShiftJis => {
+                if (0xa1..=0xdf).contains(&lead) {
+                    return Some((char::from_u32(0xff61 + lead - 0xa1)?, 1));
+                }
+                if !matches!(lead, 0x81..=0x9f | 0xe0..=0xfc)
+                    || !matches!(trail, 0x40..=0x7e | 0x80..=0xfc)
+                {
+                    return None;
+                }
+                let lead_offset = if lead < 0xa0 { 0x81 } else { 0xc1 };
+                let trail_offset = if trail < 0x7f { 0x40 } else { 0x41 };
+                let pointer = (lead - lead_offset) * 188 + trail - trail_offset;
+                if (8836..=10715).contains(&pointer) {
+                    return Some((char::from_u32(0xe000 + pointer - 8836)?, 2));
+                }
+                Some((code_point(JIS0208, pointer)?, 2))
+            }
+            Encoding::EucJp => {
+                if lead == 0x8e && (0xa1..=0xdf).contains(&trail) {
+                    return Some((char::from_u32(0xff61 + trail - 0xa1)?, 2));
+                }
+                if !(0xa1..=0xfe).contains(&lead) || !(0xa1..=0xfe).contains(&trail) {
+                    return None;
+                }
+                let pointer = (lead - 0xa1) * 94 + trail - 0xa1;
+                Some((code_point(JIS0208, pointer)?, 2))
+            }
+            Encoding::Gb18030 => {
+                if lead == 0x80 || lead == 0xff {
+                    return None;
+                }
+                if (0x30..=0x39).contains(&trail) {
+                    let third = u32::from(*bytes.get(2)?);
+                    let fourth = u32::from(*bytes.get(3)?);
+                    if !(0x81..=0xfe).contains(&third) || !(0x30..=0x39).contains(&fourth) {
+                        return None;
+                    }
+                    let pointer = (lead - 0x81) * 12600
+                        + (trail - 0x30) * 1260
+                        + (third - 0x81) * 10
+                        + fourth
+                        - 0x30;
+                    return Some((gb18030_ranges_code_point(pointer)?, 4));
+                }
+                if !matches!(trail, 0x40..=0x7e | 0x80..=0xfe) {
+                    return None;
+                }
+                let trail_offset = if trail < 0x7f { 0x40 } else { 0x41 };
+                let pointer = (lead - 0x81) * 190 + trail - trail_offset;
+                Some((code_point(GB18030, pointer)?, 2))
+            }
+            Encoding::Big5 => {
+                if !(0x81..=0xfe).contains(&lead) || !matches!(trail, 0x40..=0x7e | 0xa1..=0xfe) {
+                    return None;
+                }
+                let trail_offset = if trail < 0x7f { 0x40 } else { 0x62 };
+                let pointer = (lead - 0x81) * 157 + trail - trail_offset;
+                Some((code_point(BIG5, pointer)?, 2))
+            }
+            Encoding::EucKr => {
+                if !(0x81..=0xfe).contains(&lead) || !(0x41..=0xfe).contains(&trail) {
+                    return None;
+                }
+                let pointer = (lead - 0x81) * 190 + trail - 0x41;
+                Some((code_point(EUC_KR, pointer)?, 2))
+            }
+            Encoding::Iso2022Jp => None,
+        }
+    }
+}
+
+/// Decodes ISO-2022-JP, which switches between ASCII, JIS X 0201
+/// Roman, JIS X 0201 katakana and JIS X 0208 with escape sequences.
+fn decode_iso_2022_jp(bytes: &[u8]) -> Option<String> {
+    #[derive(Clone, Copy)]
+    enum State {
+        Ascii,
+        Roman,
+        Katakana,
+        Jis0208,
+    }
+    let mut state = State::Ascii;
+    let mut decoded = String::new();
+    let mut rest = bytes;
+    while let Some(&byte) = rest.first() {
+        if byte == ESC {
+            state = match rest.get(1..3)? {
+                b"(B" => State::Ascii,
+                b"(J" => State::Roman,
+                b"(I" => State::Katakana,
+                b"$@" | b"$B" => State::Jis0208,
+                _ => return None,
+            };
+            rest = &rest[3..];
+            continue;
+        }
+        match state {
+            State::Ascii if byte < 0x80 => decoded.push(byte as char),
+            State::Roman => decoded.push(match byte {
+                0x5c => '\u{a5}',
+                0x7e => '\u{203e}',
+                byte if byte < 0x80 => byte as char,
+                _ => return None,
+            }),
+            State::Katakana if (0x21..=0x5f).contains(&byte) => {
+                decoded.extend(char::from_u32(0xff61 - 0x21 + u32::from(byte)));
+            }
+            State::Jis0208 => {
+                let trail = *rest.get(1)?;
+                if !(0x21..=0x7e).contains(&byte) || !(0x21..=0x7e).contains(&trail) {
+                    return None;
+                }
+                let pointer = u32::from(byte - 0x21) * 94 + u32::from(trail - 0x21);
+                decoded.push(code_point(JIS0208, pointer)?);
+                rest = &rest[2..];
+                continue;
+            }
+            _ => return None,
+        }
+        rest = &rest[1..];
+    }
+    Some(decoded)
+}
+
+/// Decodes hexadecimal bytes given as `encoding:bytes`, like
+/// `shift_jis:82a0` or `big5:a4 a4`.
+pub fn decode_spec(spec: &str) -> Option<String> {
+    let (name, hex) = spec.split_once(':')?;
+    let encoding = Encoding::from_name(name)?;
+    let digits = hex
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16))
+        .collect::<Option<Vec<u32>>>()?;
+    if digits.is_empty() {
+        return None;
+    }
+    let bytes = digits
+        .chunks(2)
+        .map(|pair| match *pair {
+            [high, low] => Some((high << 4 | low) as u8),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    encoding.decode(&bytes)
+}
+
+#[test]
+fn encoding_characters() {
+    assert_eq!(Encoding::ShiftJis.encode('あ'), Some(vec![0x82, 0xa0]));
+    assert_eq!(Encoding::ShiftJis.encode('ｱ'), Some(vec![0xb1]));
+    assert_eq!(Encoding::EucJp.encode('あ'), Some(vec![0xa4, 0xa2]));
+    assert_eq!(
+        Encoding::Iso2022Jp.encode('あ'),
+        Some(vec![0x1b, 0x24, 0x42, 0x24, 0x22, 0x1b, 0x28, 0x42])
+    );
+    assert_eq!(Encoding::Gb18030.encode('中'), Some(vec![0xd6, 0xd0]));
+    assert_eq!(Encoding::Gb18030.encode('€'), Some(vec![0xa2, 0xe3]));
+    assert_eq!(
+        Encoding::Gb18030.encode('\u{80}'),
+        Some(vec![0x81, 0x30, 0x81, 0x30])
+    );
+    assert_eq!(
+        Encoding::Gb18030.encode('\u{1F600}'),
+        Some(vec![0x94, 0x39, 0xfc, 0x36])
+    );
+    assert_eq!(
+        Encoding::Gb18030.encode('\u{10000}'),
+        Some(vec![0x90, 0x30, 0x81, 0x30])
+    );
+    assert_eq!(Encoding::Big5.encode('中'), Some(vec![0xa4, 0xa4]));
+    // Ideographs that are in the index twice are encoded with their
+    // last pointer:
+    assert_eq!(Encoding::Big5.encode('十'), Some(vec![0xa4, 0x51]));
+    assert_eq!(Encoding::Big5.encode('卅'), Some(vec![0xa4, 0xca]));
+    assert_eq!(Encoding::EucKr.encode('한'), Some(vec![0xc7, 0xd1]));
+    assert_eq!(Encoding::ShiftJis.encode('한'), None);
+    assert_eq!(Encoding::ShiftJis.encode('A'), Some(vec![0x41]));
+}
+
+#[test]
+fn decoding_bytes() {
+    assert_eq!(Encoding::from_name("shift-jis"), Some(Encoding::ShiftJis));
+    assert_eq!(Encoding::from_name("GBK"), Some(Encoding::Gb18030));
+    assert_eq!(Encoding::from_name("utf-8"), None);
+    assert_eq!(decode_spec("shift_jis:82a0"), Some("あ".to_string()));
+    assert_eq!(decode_spec("sjis:82 a0 b1"), Some("あｱ".to_string()));
+    assert_eq!(decode_spec("euc-jp:a4a2"), Some("あ".to_string()));
+    assert_eq!(
+        decode_spec("iso-2022-jp:1b2442242224241b2842"),
+        Some("あい".to_string())
+    );
+    assert_eq!(decode_spec("gb18030:d6d0"), Some("中".to_string()));
+    assert_eq!(decode_spec("gb18030:81308130"), Some("\u{80}".to_string()));
+    assert_eq!(
+        decode_spec("gb18030:9439fc36"),
+        Some("\u{1F600}".to_string())
+    );
+    assert_eq!(
+        decode_spec("gb18030:8431a439"),
+        Some("\u{FFFF}".to_string())
+    );
+    assert_eq!(decode_spec("gb18030:8431a530"), None);
+    assert_eq!(
+        decode_spec("gb18030:90308130"),
+        Some("\u{10000}".to_string())
+    );
+    assert_eq!(
+        decode_spec("gb18030:e3329a35"),
+        Some("\u{10FFFF}".to_string())
+    );
+    assert_eq!(decode_spec("gb18030:e3329a36"), None);
+    assert_eq!(
+        decode_spec("gb18030:8135f437"),
+        Some("\u{E7C7}".to_string())
+    );
+    assert_eq!(decode_spec("big5:0xa4a4"), Some("中".to_string()));
+    assert_eq!(decode_spec("euc-kr:c7d1"), Some("한".to_string()));
+    assert_eq!(decode_spec("shift_jis:82"), None);
+    assert_eq!(decode_spec("shift_jis:8"), None);
+    assert_eq!(decode_spec("big5:zz"), None);
+}
+
+#[test]
+fn round_trips() {
+    for &encoding in ENCODINGS {
+        for &c in &['あ', '中', '한', '€', 'é', '\u{1F600}'] {
+            if let Some(bytes) = encoding.encode(c) {
+                assert_eq!(
+                    encoding.decode(&bytes),
+                    Some(c.to_string()),
+                    "{} in {}",
+                    c,
+                    encoding.name()
+                );
+            }
+        }
+    }
+}
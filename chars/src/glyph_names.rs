@@ -0,0 +1,98 @@
+//! PostScript glyph names, as used in fonts and PDF files, following
+//! the Adobe Glyph List specification.
+
+use std::char;
+
+include!(concat!(env!("OUT_DIR"), "/notations/glyph_names.rs"));
+
+/// Returns the glyph names for `c`: the name recommended by the AGLFN
+/// first, then other names from the AGL. Characters without a name in
+/// either list get the `uniXXXX` (or `uXXXXX` outside the BMP) name
+/// that the AGL specification derives from the code point.
+pub fn glyph_names(c: char) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    if let Ok(i) = AGLFN.binary_search_by_key(&c, |&(ch, _)| ch) {
+        names.push(AGLFN[i].1.to_string());
+    }
+    let mut buf = [0; 4];
+    let wanted: &str = c.encode_utf8(&mut buf);
+    for &(name, chars) in GLYPH_LIST {
+        if chars == wanted && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    if names.is_empty() {
+        names.push(match c as u32 {
+            cp if cp <= 0xffff => format!("uni{:04X}", cp),
+            cp => format!("u{:X}", cp),
+        });
+    }
+    names
+}
+
+fn is_uppercase_hex(hex: &str) -> bool {
+    hex.chars()
+        .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+}
+
+/// Maps one underscore-separated component of a glyph name to the
+/// characters it stands for.
+fn component_chars(component: &str) -> Option<String> {
+    if let Ok(i) = GLYPH_LIST.binary_search_by_key(&component, |&(name, _)| name) {
+        return Some(GLYPH_LIST[i].1.to_string());
+    }
+    if let Some(hex) = component.strip_prefix("uni") {
+        if hex.is_empty() || hex.len() % 4 != 0 || !is_uppercase_hex(hex) {
+            return None;
+        }
+        return (0..hex.len())
+            .step_by(4)
+            .map(|i| {
+                u32::from_str_radix(&hex[i..i + 4], 16)
+                    .ok()
+                    .and_then(char::from_u32)
+            })
+            .collect();
+    }
+    if let Some(hex) = component.strip_prefix('u') {
+        if !(4..=6).contains(&hex.len()) || !is_uppercase_hex(hex) {
+            return None;
+        }
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .map(String::from);
+    }
+    None
+}
+
+/// Returns the characters that a glyph name stands for, following the
+/// AGL specification: anything after a period is a variant suffix
+/// (`Euro.alt`), underscores separate the components of ligatures
+/// (`f_f_i`), and `uniXXXX` and `uXXXXX` name characters by their code
+/// point.
+pub fn from_glyph_name(name: &str) -> Option<String> {
+    let name = name.split('.').next()?;
+    if name.is_empty() {
+        return None;
+    }
+    name.split('_').map(component_chars).collect()
+}
+
+#[test]
+fn glyph_name_lookup() {
+    assert_eq!(glyph_names('€')[0], "Euro");
+    assert_eq!(glyph_names('≠')[0], "notequal");
+    assert_eq!(glyph_names('ﬁ'), vec!["fi"]);
+    assert_eq!(glyph_names('\u{1F600}'), vec!["u1F600"]);
+    assert_eq!(glyph_names('\u{2E3A}'), vec!["uni2E3A"]);
+    assert_eq!(from_glyph_name("Euro"), Some("€".to_string()));
+    assert_eq!(from_glyph_name("Euro.alt"), Some("€".to_string()));
+    assert_eq!(from_glyph_name("uni20AC"), Some("€".to_string()));
+    assert_eq!(from_glyph_name("uni20AC0041"), Some("€A".to_string()));
+    assert_eq!(from_glyph_name("u1F600"), Some("\u{1F600}".to_string()));
+    assert_eq!(from_glyph_name("f_f_i"), Some("ffi".to_string()));
+    assert_eq!(from_glyph_name("uni20ac"), None);
+    assert_eq!(from_glyph_name("uniD800"), None);
+    assert_eq!(from_glyph_name("nonsense"), None);
+}
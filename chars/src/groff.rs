@@ -0,0 +1,44 @@
+//! groff special characters, the escapes that roff documents (like
+//! man pages) use to write characters by code point: `\[u2014]`.
+
+use std::char;
+
+/// Returns the escape that writes `c` in groff by its code point.
+pub fn escape(c: char) -> String {
+    format!("\\[u{:04X}]", c as u32)
+}
+
+/// Returns the characters that a `uXXXX` name stands for; several code
+/// points, separated by underscores, make up a composite glyph.
+fn from_code_points(name: &str) -> Option<String> {
+    name.strip_prefix('u')?
+        .split('_')
+        .map(|hex| {
+            let valid = (4..=6).contains(&hex.len())
+                && hex
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase());
+            if !valid {
+                return None;
+            }
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        })
+        .collect()
+}
+
+/// Returns the characters that a special character escape naming code
+/// points, `\[uXXXX]` or `\[uXXXX_YYYY]`, stands for.
+pub fn from_escape(spec: &str) -> Option<String> {
+    from_code_points(spec.strip_prefix("\\[")?.strip_suffix(']')?)
+}
+
+#[test]
+fn groff_escapes() {
+    assert_eq!(escape('—'), "\\[u2014]");
+    assert_eq!(escape('\u{1F600}'), "\\[u1F600]");
+    assert_eq!(from_escape("\\[u2014]"), Some("—".to_string()));
+    assert_eq!(from_escape("\\[u0065_0301]"), Some("e\u{301}".to_string()));
+    assert_eq!(from_escape("\\[u2014"), None);
+    assert_eq!(from_escape("\\[u20ac]"), None);
+    assert_eq!(from_escape("\\(em"), None);
+}
@@ -1,6 +1,13 @@
 use std::char;
 use std::cmp::Reverse;
 
+use super::codepages;
+use super::encodings;
+use super::glyph_names;
+use super::groff;
+use super::keysyms;
+use super::latex;
+use super::typing;
 use super::unicode;
 
 const READ_BASES: &[u32] = &[16, 10, 8, 2];
@@ -8,9 +15,14 @@ const READ_BASES: &[u32] = &[16, 10, 8, 2];
 /// Takes a stringly description of a character (the character itself,
 /// or a unicode code point name) and returns a Vec of Describable
 /// elements that hold the corresponding character. The elements of
-/// the vector are sorted by descending numeric code point.
+/// the vector are sorted by descending numeric code point, except
+/// that the characters a spec decodes to in sequence (like the
+/// ligature `f_f_i`) come first, in their order, and ideographs found
+/// by their definition come last.
 pub fn from_arg(spec: &str) -> Vec<char> {
     let mut chars: Vec<char> = Vec::new();
+    let mut sequence: Vec<char> = Vec::new();
+    let mut definitions: Vec<char> = Vec::new();
     let mut try_names = true;
 
     // match the character itself, or any of its names:
@@ -32,6 +44,56 @@ pub fn from_arg(spec: &str) -> Vec<char> {
             .map(|num| char::from_u32(num).map(|c| chars.push(c)));
     }
 
+    // Compose Hangul syllables from their jamo:
+    if let Some(jamo) = spec.strip_prefix("hangul:") {
+        chars.append(unicode::hangul::from_spec(jamo).as_mut());
+        try_names = false;
+    }
+
+    // Match Vim digraphs:
+    if let Some(digraph) = spec.strip_prefix("digraph:") {
+        chars.extend(typing::from_digraph(digraph));
+        try_names = false;
+    }
+
+    // Match LaTeX commands:
+    if spec.starts_with('\\') {
+        if let Some(c) = latex::from_command(spec) {
+            chars.push(c);
+            try_names = false;
+        }
+    }
+
+    // Decode bytes in legacy code pages, like cp1252:0x80:
+    if let Some(c) = codepages::decode_spec(spec) {
+        chars.push(c);
+        try_names = false;
+    }
+
+    // Decode byte sequences in East Asian encodings, like
+    // shift_jis:82a0:
+    if let Some(decoded) = encodings::decode_spec(spec) {
+        sequence.extend(decoded.chars());
+        try_names = false;
+    }
+
+    // Match groff escapes of code points:
+    if let Some(escaped) = groff::from_escape(spec) {
+        sequence.extend(escaped.chars());
+        try_names = false;
+    }
+
+    // Match X11 keysym names:
+    chars.extend(keysyms::from_name(spec));
+
+    // Match PostScript glyph names; ligatures stand for several
+    // characters:
+    if !spec.contains(char::is_whitespace) {
+        if let Some(glyph) = glyph_names::from_glyph_name(spec) {
+            sequence.extend(glyph.chars());
+        }
+    }
+
     // Match ^-escapes as control characters
     if spec.len() == 2 && spec.starts_with('^') {
         let control = spec.as_bytes()[1];
@@ -45,11 +107,42 @@ pub fn from_arg(spec: &str) -> Vec<char> {
     // Match characters from all our name tables:
     if try_names {
         chars.append(unicode::lookup_by_query(spec).as_mut());
+        definitions = unicode::lookup_by_definition(spec);
     }
 
     chars.sort_by_key(|&k| Reverse(k));
     chars.dedup();
-    chars
+    chars.retain(|c| !sequence.contains(c));
+    sequence.append(&mut chars);
+    definitions.retain(|c| !sequence.contains(c));
+    sequence.append(&mut definitions);
+    sequence
+}
+
+/// Like `from_arg`, but returns code points instead of characters, so
+/// that surrogate code points (which can't be a `char`) are found by
+/// their number, too.
+pub fn code_points_from_arg(spec: &str) -> Vec<u32> {
+    let mut code_points: Vec<u32> = from_arg(spec).into_iter().map(u32::from).collect();
+    let mut numbers: Vec<u32> = vec![];
+    if spec.starts_with("0x") || spec.starts_with("U+") {
+        numbers.extend(u32::from_str_radix(&spec[2..], 16).ok());
+    }
+    for base in READ_BASES {
+        numbers.extend(u32::from_str_radix(spec, *base).ok());
+    }
+    let surrogates: Vec<u32> = numbers
+        .into_iter()
+        .filter(|&cp| unicode::surrogates::is_surrogate(cp))
+        .collect();
+    // Keep the order of decoded sequences unless there are surrogates
+    // to sort in:
+    if !surrogates.is_empty() {
+        code_points.extend(surrogates);
+        code_points.sort_by_key(|&k| Reverse(k));
+        code_points.dedup();
+    }
+    code_points
 }
 
 #[test]
@@ -87,3 +180,88 @@ fn from_arg_translates_controls() {
     assert_eq!(0x03 as char, from_arg("^c")[0]);
     assert_eq!(0x03 as char, from_arg("^C")[0]);
 }
+
+#[test]
+fn from_arg_composes_hangul() {
+    assert_eq!(vec!['한'], from_arg("hangul:han"));
+    assert_eq!(vec!['한'], from_arg("hangul:h+a+n"));
+    assert_eq!(vec!['한'], from_arg("hangul:\u{1112}\u{1161}\u{11ab}"));
+    assert_eq!(
+        vec!['한'],
+        from_arg("hangul:hangul choseong hieuh+hangul jungseong a+hangul jongseong nieun")
+    );
+}
+
+#[test]
+fn from_arg_finds_ideographs() {
+    assert!(from_arg("pinyin:han").contains(&'漢'));
+    assert!(from_arg("pinyin:hàn").contains(&'漢'));
+    assert!(from_arg("definition:water").contains(&'水'));
+    assert!(from_arg("definition:milky way").contains(&'漢'));
+    assert_eq!(vec!['漢'], from_arg("definition:dynasty han"));
+    // Characters named so come before ideographs defined so:
+    let water = from_arg("water");
+    assert!(water.iter().position(|&c| c == '🌊') < water.iter().position(|&c| c == '水'));
+    let milky_way = from_arg("milky way");
+    assert_eq!(Some(&'🌌'), milky_way.first());
+    assert!(milky_way.contains(&'漢'));
+    assert_eq!(vec!['漢'], from_arg("radical:85 strokes:11"));
+}
+
+#[test]
+fn from_arg_resolves_digraphs() {
+    assert_eq!(vec!['ä'], from_arg("digraph:a:"));
+    assert_eq!(vec!['→'], from_arg("digraph:->"));
+    assert!(from_arg("digraph:qq").is_empty());
+}
+
+#[test]
+fn from_arg_resolves_latex_commands() {
+    assert_eq!(vec!['→'], from_arg("\\rightarrow"));
+    assert_eq!(vec!['ℝ'], from_arg("\\mathbb{R}"));
+    assert_eq!(vec!['ö'], from_arg("\\\"{o}"));
+}
+
+#[test]
+fn from_arg_resolves_keysyms() {
+    assert_eq!(vec!['λ'], from_arg("Greek_lambda"));
+    assert_eq!(vec!['€'], from_arg("XK_EuroSign"));
+    assert!(from_arg("U20AC").contains(&'€'));
+}
+
+#[test]
+fn from_arg_resolves_glyph_names() {
+    assert!(from_arg("Euro").contains(&'€'));
+    assert_eq!(vec!['€'], from_arg("uni20AC"));
+    assert_eq!(vec!['f', 'f', 'i'], from_arg("f_f_i"));
+    assert_eq!(vec![0x66, 0x66, 0x69], code_points_from_arg("f_f_i"));
+}
+
+#[test]
+fn from_arg_resolves_groff_escapes() {
+    assert_eq!(vec!['—'], from_arg("\\[u2014]"));
+    assert_eq!(vec!['A', '\u{300}'], from_arg("\\[u0041_0300]"));
+}
+
+#[test]
+fn from_arg_decodes_code_page_bytes() {
+    assert_eq!(vec!['€'], from_arg("cp1252:0x80"));
+    assert_eq!(vec!['A'], from_arg("ebcdic:0xC1"));
+}
+
+#[test]
+fn from_arg_decodes_cjk_encodings() {
+    assert_eq!(vec!['あ'], from_arg("shift_jis:82a0"));
+    assert_eq!(vec!['中'], from_arg("big5:a4 a4"));
+    assert_eq!(vec!['한'], from_arg("euc-kr:0xc7d1"));
+    assert_eq!(vec!['あ', 'あ'], from_arg("sjis:82a0 82a0"));
+    assert_eq!(vec!['あ', 'い'], from_arg("sjis:82a0 82a2"));
+}
+
+#[test]
+fn code_points_from_arg_finds_surrogates() {
+    assert_eq!(vec![0xD800], code_points_from_arg("U+D800"));
+    assert_eq!(vec![0xDE00], code_points_from_arg("0xde00"));
+    assert!(from_arg("U+D800").is_empty());
+    assert_eq!(vec![0x41], code_points_from_arg("0x41"));
+}
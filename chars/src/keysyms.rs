@@ -0,0 +1,53 @@
+//! X11 keysyms: the names that keyboard layouts and key bindings use
+//! for the characters that keys type.
+
+use std::char;
+
+include!(concat!(env!("OUT_DIR"), "/notations/keysyms.rs"));
+
+/// Keysyms for characters without a named keysym are the code point
+/// plus this offset.
+const UNICODE_OFFSET: u32 = 0x0100_0000;
+
+/// Returns the named keysyms for `c`, with their values.
+pub fn keysyms(c: char) -> impl Iterator<Item = (&'static str, u32)> {
+    let start = KEYSYMS.partition_point(|&(ch, _, _)| ch < c);
+    let end = KEYSYMS.partition_point(|&(ch, _, _)| ch <= c);
+    KEYSYMS[start..end]
+        .iter()
+        .map(|&(_, name, value)| (name, value))
+}
+
+/// Returns the Unicode keysym for `c`, which every character has even
+/// if it has no named keysym.
+pub fn unicode_keysym(c: char) -> (String, u32) {
+    (format!("U{:04X}", c as u32), c as u32 + UNICODE_OFFSET)
+}
+
+/// Returns the character that a keysym name (like `Greek_lambda`,
+/// `XK_EuroSign` or `U20AC`) stands for.
+pub fn from_name(name: &str) -> Option<char> {
+    let name = name.strip_prefix("XK_").unwrap_or(name);
+    if let Some(&(c, _, _)) = KEYSYMS.iter().find(|&&(_, n, _)| n == name) {
+        return Some(c);
+    }
+    let hex = name.strip_prefix('U')?;
+    if hex.len() < 4 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+#[test]
+fn keysym_lookup() {
+    assert!(keysyms('λ').any(|(name, _)| name == "Greek_lambda"));
+    assert_eq!(keysyms('€').collect::<Vec<_>>(), vec![("EuroSign", 0x20ac)]);
+    assert_eq!(keysyms('≠').next(), Some(("notequal", 0x8bd)));
+    assert_eq!(keysyms('🙂').next(), None);
+    assert_eq!(unicode_keysym('🙂'), ("U1F642".to_string(), 0x101f642));
+    assert_eq!(from_name("Greek_lambda"), Some('λ'));
+    assert_eq!(from_name("XK_EuroSign"), Some('€'));
+    assert_eq!(from_name("U20AC"), Some('€'));
+    assert_eq!(from_name("U20"), None);
+    assert_eq!(from_name("greek_lambda"), None);
+}
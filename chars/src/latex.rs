@@ -0,0 +1,60 @@
+//! LaTeX commands for characters: math symbols from LaTeX and its
+//! common packages, text-mode accents and text symbols.
+
+include!(concat!(env!("OUT_DIR"), "/notations/latex.rs"));
+
+/// Returns the LaTeX commands that produce `c`, the preferred one
+/// first.
+pub fn commands(c: char) -> &'static [&'static str] {
+    LATEX_COMMANDS
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .map(|i| LATEX_COMMANDS[i].1)
+        .unwrap_or(&[])
+}
+
+/// Writes a command with an argument in the form of the table, which
+/// always puts the argument in braces: `\"o` and `\mathbb R` become
+/// `\"{o}` and `\mathbb{R}`.
+fn with_braces(command: &str) -> Option<String> {
+    let rest = command.strip_prefix('\\')?;
+    let name_len = match rest.chars().next()? {
+        c if c.is_ascii_alphabetic() => rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len()),
+        c => c.len_utf8(),
+    };
+    let (name, argument) = rest.split_at(name_len);
+    let argument = argument.trim_start();
+    if argument.is_empty() || argument.starts_with('{') {
+        return None;
+    }
+    Some(format!("\\{}{{{}}}", name, argument))
+}
+
+/// Returns the character that a LaTeX command produces.
+pub fn from_command(command: &str) -> Option<char> {
+    let command = command.trim();
+    let find = |wanted: &str| {
+        LATEX_COMMANDS
+            .iter()
+            .find(|&&(_, commands)| commands.contains(&wanted))
+            .map(|&(c, _)| c)
+    };
+    find(command).or_else(|| find(&with_braces(command)?))
+}
+
+#[test]
+fn latex_commands() {
+    assert_eq!(commands('→'), &["\\rightarrow", "\\to"]);
+    assert!(commands('ö').contains(&"\\\"{o}"));
+    assert!(commands('ß').contains(&"\\ss"));
+    assert!(commands('a').is_empty());
+    assert_eq!(from_command("\\rightarrow"), Some('→'));
+    assert_eq!(from_command("\\alpha"), Some('α'));
+    assert_eq!(from_command("\\mathbb{R}"), Some('ℝ'));
+    assert_eq!(from_command("\\mathbb R"), Some('ℝ'));
+    assert_eq!(from_command("\\\"{o}"), Some('ö'));
+    assert_eq!(from_command("\\\"o"), Some('ö'));
+    assert_eq!(from_command("\\c c"), Some('ç'));
+    assert_eq!(from_command("\\nonsense"), None);
+}
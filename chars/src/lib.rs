@@ -4,7 +4,17 @@
 extern crate lazy_static;
 
 mod ascii;
+mod codepages;
+mod encodings;
+mod glyph_names;
+mod groff;
+mod keysyms;
+mod latex;
+mod mojibake;
+mod typing;
 mod unicode;
 
+pub mod check;
 pub mod display;
 pub mod human_names;
+pub mod validate;
@@ -1,18 +1,158 @@
 use std::env;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
 
+use chars::check;
 use chars::display;
 use chars::human_names;
+use chars::validate;
 
-fn main() {
-    let args = env::args().skip(1);
+/// Default allowlist file for `--check`, looked up in the current directory.
+const ALLOWLIST_FILE: &str = ".chars-allowlist";
+
+fn describe_args(args: &[String]) {
+    let mut high_surrogate: Option<u32> = None;
     for argument in args {
-        let results = human_names::from_arg(argument.as_ref());
+        let results = human_names::code_points_from_arg(argument.as_ref());
         if results.is_empty() {
             eprintln!("No results for “{}”.", argument);
-        } else {
-            for c in results {
-                display::describe(c);
+        }
+        for &cp in &results {
+            match std::char::from_u32(cp) {
+                Some(c) => display::describe(c),
+                None => display::describe_surrogate(cp),
+            }
+        }
+        // A high surrogate followed by a low one forms a pair:
+        if let Some(high) = high_surrogate {
+            if let Some(&low) = results.iter().find(|&&cp| (0xDC00..=0xDFFF).contains(&cp)) {
+                display::describe_surrogate_pair(high, low);
+            }
+        }
+        high_surrogate = results
+            .iter()
+            .cloned()
+            .find(|cp| (0xD800..=0xDBFF).contains(cp));
+    }
+}
+
+/// Runs `chars --check [--allowlist FILE] FILE...` and returns the
+/// process exit code: 0 if all files are clean, 1 if anything
+/// suspicious was found and 2 if no files were given or a file couldn't
+/// be read.
+fn check_files(mut args: &[String]) -> i32 {
+    let mut allowlist_file = Path::new(ALLOWLIST_FILE);
+    let mut explicit_allowlist = false;
+    if args.len() >= 2 && args[0] == "--allowlist" {
+        allowlist_file = Path::new(&args[1]);
+        explicit_allowlist = true;
+        args = &args[2..];
+    }
+    if args.is_empty() {
+        eprintln!("Usage: chars --check [--allowlist FILE] FILE...");
+        return 2;
+    }
+    let allowlist = if explicit_allowlist || allowlist_file.exists() {
+        match check::Allowlist::from_file(allowlist_file) {
+            Ok(allowlist) => allowlist,
+            Err(e) => {
+                eprintln!(
+                    "Could not read allowlist {}: {}",
+                    allowlist_file.display(),
+                    e
+                );
+                return 2;
+            }
+        }
+    } else {
+        check::Allowlist::new()
+    };
+
+    let mut status = 0;
+    for path in args {
+        match check::check_file(Path::new(path), &allowlist) {
+            Ok(findings) => {
+                for finding in findings {
+                    println!("{}:{}", path, finding);
+                    status = status.max(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                status = 2;
+            }
+        }
+    }
+    status
+}
+
+/// Runs `chars --validate FILE...` and returns the process exit code:
+/// 0 if all files are valid UTF-8, 1 if any aren't and 2 if no files
+/// were given or a file couldn't be read.
+fn validate_files(args: &[String]) -> i32 {
+    if args.is_empty() {
+        eprintln!("Usage: chars --validate FILE...");
+        return 2;
+    }
+    let mut status = 0;
+    for path in args {
+        match validate::validate_file(Path::new(path)) {
+            Ok(invalid) => {
+                for invalid in invalid {
+                    println!("{}:{}", path, invalid);
+                    status = status.max(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                status = 2;
+            }
+        }
+    }
+    status
+}
+
+/// Runs `chars --mojibake` on standard input, which can hold what
+/// arguments can't, like the NULs of UTF-16 text read as UTF-8.
+fn describe_mojibake_input() -> i32 {
+    let mut input = vec![];
+    if let Err(e) = io::stdin().read_to_end(&mut input) {
+        eprintln!("Could not read standard input: {}", e);
+        return 2;
+    }
+    let input = String::from_utf8_lossy(&input);
+    display::describe_mojibake(input.trim_end_matches('\n'));
+    0
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("--check") => process::exit(check_files(&args[1..])),
+        Some("--validate") => process::exit(validate_files(&args[1..])),
+        Some("--skeleton") => display::describe_skeletons(&args[1..]),
+        Some("--normalize") => {
+            for s in &args[1..] {
+                display::describe_normalizations(s);
+            }
+        }
+        Some("--mojibake") if args.len() == 1 => process::exit(describe_mojibake_input()),
+        Some("--mojibake") => {
+            for s in &args[1..] {
+                display::describe_mojibake(s);
+            }
+        }
+        Some("--simplified") => {
+            for s in &args[1..] {
+                display::describe_conversion(s, "kSimplifiedVariant");
+            }
+        }
+        Some("--traditional") => {
+            for s in &args[1..] {
+                display::describe_conversion(s, "kTraditionalVariant");
             }
         }
+        _ => describe_args(&args),
     }
 }
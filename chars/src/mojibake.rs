@@ -0,0 +1,315 @@
+//! Diagnoses mojibake: text whose bytes were decoded in the wrong
+//! encoding, like "CafÃ©" for "Café" written in UTF-8 and read as
+//! CP1252.
+
+use std::char;
+use std::str;
+
+use super::codepages;
+
+/// The ways text commonly gets garbled: written in one encoding and
+/// read in another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Misreading {
+    Cp1252,
+    Latin1,
+    Utf16Le,
+    Utf16Be,
+}
+
+const MISREADINGS: &[Misreading] = &[
+    Misreading::Cp1252,
+    Misreading::Latin1,
+    Misreading::Utf16Le,
+    Misreading::Utf16Be,
+];
+
+/// How many times text is assumed to have been garbled at most.
+const MAX_DEPTH: usize = 3;
+
+/// A character of the repaired text.
+pub struct Piece {
+    /// The original character, or `None` if its bytes were damaged.
+    pub original: Option<char>,
+    /// The bytes that encoded it.
+    pub bytes: Vec<u8>,
+}
+
+/// A possible original of garbled text.
+pub struct Repair {
+    pub misreading: Misreading,
+    /// How many times the text was garbled.
+    pub depth: usize,
+    pub pieces: Vec<Piece>,
+}
+
+impl Repair {
+    pub fn text(&self) -> String {
+        self.pieces
+            .iter()
+            .map(|piece| piece.original.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+
+    fn damaged(&self) -> usize {
+        self.pieces.iter().filter(|p| p.original.is_none()).count()
+    }
+}
+
+impl Misreading {
+    /// The encoding the text was written in.
+    pub fn written_as(self) -> &'static str {
+        match self {
+            Misreading::Cp1252 | Misreading::Latin1 => "UTF-8",
+            Misreading::Utf16Le => "UTF-16LE",
+            Misreading::Utf16Be => "UTF-16BE",
+        }
+    }
+
+    /// The encoding the text was read as.
+    pub fn read_as(self) -> &'static str {
+        match self {
+            Misreading::Cp1252 => "CP1252",
+            Misreading::Latin1 => "ISO-8859-1",
+            Misreading::Utf16Le | Misreading::Utf16Be => "UTF-8",
+        }
+    }
+
+    /// Encodes `s` the way it was written.
+    pub fn encode(self, s: &str) -> Vec<u8> {
+        match self {
+            Misreading::Cp1252 | Misreading::Latin1 => s.as_bytes().to_vec(),
+            Misreading::Utf16Le => s.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            Misreading::Utf16Be => s.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        }
+    }
+
+    /// Decodes `bytes` the wrong way, garbling them.
+    pub fn misread(self, bytes: &[u8]) -> String {
+        match self {
+            Misreading::Cp1252 => {
+                let cp1252 = codepages::lookup("CP1252").unwrap();
+                // Bytes that CP1252 leaves undefined are usually passed
+                // through as C1 controls:
+                bytes
+                    .iter()
+                    .map(|&byte| cp1252.chars[byte as usize].unwrap_or(byte as char))
+                    .collect()
+            }
+            Misreading::Latin1 => bytes.iter().map(|&byte| byte as char).collect(),
+            Misreading::Utf16Le | Misreading::Utf16Be => {
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+        }
+    }
+
+    /// Returns the bytes that were misread as `s`, if there are any.
+    fn unmisread(self, s: &str) -> Option<Vec<u8>> {
+        match self {
+            Misreading::Cp1252 => {
+                let cp1252 = codepages::lookup("CP1252").unwrap();
+                s.chars()
+                    .map(|c| {
+                        codepages::encode(cp1252, c).or_else(|| {
+                            Some(c as u32)
+                                .filter(|&cp| cp < 0x100 && cp1252.chars[cp as usize].is_none())
+                                .map(|cp| cp as u8)
+                        })
+                    })
+                    .collect()
+            }
+            Misreading::Latin1 => s
+                .chars()
+                .map(|c| Some(c as u32).filter(|&cp| cp < 0x100).map(|cp| cp as u8))
+                .collect(),
+            // A byte that wasn't valid UTF-8 was replaced with U+FFFD,
+            // so it's lost. It's marked with 0xFF, which UTF-8 never
+            // uses:
+            Misreading::Utf16Le | Misreading::Utf16Be => {
+                let mut bytes = vec![];
+                for c in s.chars() {
+                    match c {
+                        char::REPLACEMENT_CHARACTER => bytes.push(0xff),
+                        c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                    }
+                }
+                Some(bytes)
+            }
+        }
+    }
+
+    /// Decodes `bytes` the way they were written, keeping track of the
+    /// bytes of each character.
+    fn decode(self, mut bytes: &[u8]) -> Vec<Piece> {
+        let mut pieces = vec![];
+        match self {
+            Misreading::Cp1252 | Misreading::Latin1 => {
+                while !bytes.is_empty() {
+                    let (valid, invalid) = match str::from_utf8(bytes) {
+                        Ok(valid) => (valid, 0),
+                        Err(e) => (
+                            str::from_utf8(&bytes[..e.valid_up_to()]).unwrap(),
+                            e.error_len().unwrap_or(bytes.len() - e.valid_up_to()),
+                        ),
+                    };
+                    for c in valid.chars() {
+                        let len = c.len_utf8();
+                        pieces.push(Piece {
+                            original: Some(c),
+                            bytes: bytes[..len].to_vec(),
+                        });
+                        bytes = &bytes[len..];
+                    }
+                    if invalid > 0 {
+                        pieces.push(Piece {
+                            original: None,
+                            bytes: bytes[..invalid].to_vec(),
+                        });
+                        bytes = &bytes[invalid..];
+                    }
+                }
+            }
+            Misreading::Utf16Le | Misreading::Utf16Be => {
+                let units = bytes.chunks(2).map(|pair| match *pair {
+                    // Lost bytes and an odd byte at the end can't be
+                    // decoded, so they're made into unpaired surrogates:
+                    [0xff, _] | [_, 0xff] | [_] => 0xDC00,
+                    [first, second] if self == Misreading::Utf16Le => {
+                        u16::from_le_bytes([first, second])
+                    }
+                    [first, second] => u16::from_be_bytes([first, second]),
+                    _ => unreachable!(),
+                });
+                for result in char::decode_utf16(units) {
+                    let len = result.as_ref().map_or(2, |&c| c.len_utf16() * 2);
+                    let len = len.min(bytes.len());
+                    pieces.push(Piece {
+                        original: result.ok(),
+                        bytes: bytes[..len].to_vec(),
+                    });
+                    bytes = &bytes[len..];
+                }
+            }
+        }
+        pieces
+    }
+
+    /// Undoes one layer of this misreading, if `s` looks like the
+    /// result of it.
+    fn repair(self, s: &str, depth: usize) -> Option<Repair> {
+        let pieces = self.decode(&self.unmisread(s)?);
+        let repair = Repair {
+            misreading: self,
+            depth,
+            pieces,
+        };
+        let repaired = match self {
+            // Only sequences of several bytes show that the text was
+            // really UTF-8:
+            Misreading::Cp1252 | Misreading::Latin1 => repair
+                .pieces
+                .iter()
+                .filter(|p| p.original.is_some() && p.bytes.len() > 1)
+                .count(),
+            // UTF-16 read as UTF-8 turns the upper halves of Latin
+            // characters into NULs, and reading it in the wrong byte
+            // order gives no Latin characters at all:
+            Misreading::Utf16Le | Misreading::Utf16Be => {
+                if !s.contains('\0') || repair.text().contains('\0') {
+                    return None;
+                }
+                repair
+                    .pieces
+                    .iter()
+                    .filter(|p| matches!(p.original, Some(c) if (c as u32) < 0x100))
+                    .count()
+            }
+        };
+        if repaired == 0 || repair.damaged() > repaired {
+            return None;
+        }
+        Some(repair)
+    }
+}
+
+/// Returns the possible originals of `s`, most plausible first.
+pub fn repairs(s: &str) -> Vec<Repair> {
+    let mut repairs: Vec<Repair> = vec![];
+    for &misreading in MISREADINGS {
+        let mut text = s.to_string();
+        for depth in 1..=MAX_DEPTH {
+            match misreading.repair(&text, depth) {
+                Some(repair) => {
+                    text = repair.text();
+                    repairs.push(repair);
+                }
+                None => break,
+            }
+            // UTF-16 can't be misread as UTF-8 twice:
+            if let Misreading::Utf16Le | Misreading::Utf16Be = misreading {
+                break;
+            }
+        }
+    }
+    // Text that was garbled several times is repaired once per layer,
+    // so the shortest result with the fewest damaged characters is the
+    // most complete repair:
+    repairs.sort_by_key(|repair| (repair.damaged(), repair.pieces.len()));
+    let mut seen: Vec<String> = vec![];
+    repairs.retain(|repair| {
+        let text = repair.text();
+        if seen.contains(&text) {
+            return false;
+        }
+        seen.push(text);
+        true
+    });
+    repairs
+}
+
+#[test]
+fn repairing_utf8_read_as_cp1252() {
+    let repairs = repairs("CafÃ©");
+    assert_eq!(repairs[0].text(), "Café");
+    assert_eq!(repairs[0].misreading, Misreading::Cp1252);
+    assert_eq!(repairs[0].depth, 1);
+    assert_eq!(repairs[0].pieces[3].bytes, vec![0xc3, 0xa9]);
+
+    let repairs = self::repairs("â€œquotedâ€\u{9d}");
+    assert_eq!(repairs[0].text(), "“quoted”");
+    // The last byte of ” is undefined in CP1252, and often lost:
+    assert_eq!(self::repairs("â€œquotedâ€")[0].text(), "“quoted\u{FFFD}");
+}
+
+#[test]
+fn repairing_double_encoding() {
+    let repairs = repairs("CafÃƒÂ©");
+    assert_eq!(repairs[0].text(), "Café");
+    assert_eq!(repairs[0].depth, 2);
+}
+
+#[test]
+fn repairing_utf16_read_as_utf8() {
+    let repairs = repairs("C\0a\0f\0");
+    assert_eq!(repairs[0].text(), "Caf");
+    assert_eq!(repairs[0].misreading, Misreading::Utf16Le);
+    // The first byte of é (e9 00) isn't valid UTF-8:
+    let repairs = self::repairs("C\0a\0f\0\u{FFFD}\0");
+    assert_eq!(repairs[0].text(), "Caf\u{FFFD}");
+}
+
+#[test]
+fn garbling() {
+    let garbled = Misreading::Cp1252.misread(&Misreading::Cp1252.encode("é"));
+    assert_eq!(garbled, "Ã©");
+    let garbled = Misreading::Utf16Be.misread(&Misreading::Utf16Be.encode("A"));
+    assert_eq!(garbled, "\0A");
+    assert_eq!(Misreading::Utf16Le.encode("A€"), vec![0x41, 0, 0xac, 0x20]);
+}
+
+#[test]
+fn leaving_correct_text_alone() {
+    assert!(repairs("Café").is_empty());
+    assert!(repairs("plain ASCII").is_empty());
+    assert!(repairs("“quoted”").is_empty());
+}
@@ -0,0 +1,225 @@
+//! How to type a character: X11 Compose key sequences, and the
+//! Unicode input methods of GTK, Windows and macOS.
+
+use super::codepages;
+use super::unicode::normalization;
+
+include!(concat!(env!("OUT_DIR"), "/input/compose.rs"));
+include!(concat!(env!("OUT_DIR"), "/input/digraphs.rs"));
+
+/// The characters that the US keyboard layout on macOS types with
+/// the Option key, with and without Shift, by the key they're on.
+const MACOS_OPTION: &[(char, char, bool)] = &[
+    ('å', 'A', false),
+    ('∫', 'B', false),
+    ('ç', 'C', false),
+    ('∂', 'D', false),
+    ('ƒ', 'F', false),
+    ('©', 'G', false),
+    ('˙', 'H', false),
+    ('∆', 'J', false),
+    ('˚', 'K', false),
+    ('¬', 'L', false),
+    ('µ', 'M', false),
+    ('ø', 'O', false),
+    ('π', 'P', false),
+    ('œ', 'Q', false),
+    ('®', 'R', false),
+    ('ß', 'S', false),
+    ('†', 'T', false),
+    ('√', 'V', false),
+    ('∑', 'W', false),
+    ('≈', 'X', false),
+    ('¥', 'Y', false),
+    ('Ω', 'Z', false),
+    ('¡', '1', false),
+    ('™', '2', false),
+    ('£', '3', false),
+    ('¢', '4', false),
+    ('∞', '5', false),
+    ('§', '6', false),
+    ('¶', '7', false),
+    ('•', '8', false),
+    ('ª', '9', false),
+    ('º', '0', false),
+    ('–', '-', false),
+    ('≠', '=', false),
+    ('“', '[', false),
+    ('‘', ']', false),
+    ('«', '\\', false),
+    ('…', ';', false),
+    ('æ', '\'', false),
+    ('≤', ',', false),
+    ('≥', '.', false),
+    ('÷', '/', false),
+    ('Å', 'A', true),
+    ('ı', 'B', true),
+    ('Ç', 'C', true),
+    ('´', 'E', true),
+    ('˝', 'G', true),
+    ('ˆ', 'I', true),
+    ('\u{f8ff}', 'K', true),
+    ('˜', 'N', true),
+    ('Ø', 'O', true),
+    ('∏', 'P', true),
+    ('Œ', 'Q', true),
+    ('‰', 'R', true),
+    ('ˇ', 'T', true),
+    ('¨', 'U', true),
+    ('◊', 'V', true),
+    ('„', 'W', true),
+    ('˛', 'X', true),
+    ('¸', 'Z', true),
+    ('⁄', '1', true),
+    ('€', '2', true),
+    ('‹', '3', true),
+    ('›', '4', true),
+    ('ﬁ', '5', true),
+    ('ﬂ', '6', true),
+    ('‡', '7', true),
+    ('°', '8', true),
+    ('·', '9', true),
+    ('‚', '0', true),
+    ('—', '-', true),
+    ('±', '=', true),
+    ('”', '[', true),
+    ('’', ']', true),
+    ('»', '\\', true),
+    ('Æ', '\'', true),
+    ('¯', ',', true),
+    ('˘', '.', true),
+    ('¿', '/', true),
+];
+
+/// The dead keys of the US keyboard layout on macOS: the key that
+/// is pressed with Option, the combining mark it adds, and the
+/// letters it combines with.
+const MACOS_DEAD_KEYS: &[(char, char, &str)] = &[
+    ('`', '\u{300}', "aeiouAEIOU"),
+    ('E', '\u{301}', "aeiouAEIOU"),
+    ('I', '\u{302}', "aeiouAEIOU"),
+    ('N', '\u{303}', "anoANO"),
+    ('U', '\u{308}', "aeiouyAEIOUY"),
+];
+
+/// Returns the key sequences that type `c` after pressing the
+/// Compose key, shortest first, with keys separated by spaces.
+pub fn compose_sequences(c: char) -> &'static [&'static str] {
+    COMPOSE_SEQUENCES
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .map(|i| COMPOSE_SEQUENCES[i].1)
+        .unwrap_or(&[])
+}
+
+/// Returns the Vim digraphs of `c`, which type it after Ctrl+K.
+pub fn digraphs(c: char) -> impl Iterator<Item = &'static str> {
+    let start = DIGRAPHS.partition_point(|&(ch, _)| ch < c);
+    let end = DIGRAPHS.partition_point(|&(ch, _)| ch <= c);
+    DIGRAPHS[start..end].iter().map(|&(_, digraph)| digraph)
+}
+
+/// Returns the character that a digraph stands for. Like Vim, this
+/// tries the two characters in the other order if there is no digraph
+/// in the given order.
+pub fn from_digraph(digraph: &str) -> Option<char> {
+    let reversed: String = digraph.chars().rev().collect();
+    [digraph, reversed.as_str()].iter().find_map(|wanted| {
+        DIGRAPHS
+            .iter()
+            .find(|&&(_, digraph)| digraph == *wanted)
+            .map(|&(c, _)| c)
+    })
+}
+
+/// Returns how to type `c` in GTK applications, which take the
+/// hexadecimal code point after Ctrl+Shift+U.
+pub fn gtk_sequence(c: char) -> String {
+    format!("Ctrl+Shift+U, {:x}, Space", c as u32)
+}
+
+/// Returns how to type `c` in Windows applications that convert the
+/// hexadecimal code point before the cursor with Alt+X (like Word
+/// and WordPad).
+pub fn windows_alt_x(c: char) -> String {
+    format!("{:04X}, Alt+X", c as u32)
+}
+
+/// Returns the Alt codes that type `c` on the numeric keypad on a
+/// US Windows system: codes with a leading zero pick from the ANSI
+/// code page (CP1252), codes without one from the OEM code page
+/// (CP437). Only the upper halves are listed, the lower ones are
+/// ASCII.
+pub fn windows_alt_codes(c: char) -> Vec<String> {
+    let mut codes = vec![];
+    for &(name, prefix) in &[("CP1252", "0"), ("CP437", "")] {
+        if let Some(byte) = codepages::lookup(name).and_then(|cp| codepages::encode(cp, c)) {
+            if byte >= 0x80 {
+                codes.push(format!("Alt+{}{} ({})", prefix, byte, name));
+            }
+        }
+    }
+    codes
+}
+
+fn macos_key(key: char, shift: bool) -> String {
+    if shift {
+        format!("Shift+{}", key)
+    } else {
+        key.to_string()
+    }
+}
+
+/// Returns how to type `c` with the Option key on the US keyboard
+/// layout on macOS, directly or with a dead key followed by a letter.
+pub fn macos_sequence(c: char) -> Option<String> {
+    if let Some(&(_, key, shift)) = MACOS_OPTION.iter().find(|&&(ch, _, _)| ch == c) {
+        return Some(format!("Option+{}", macos_key(key, shift)));
+    }
+    let decomposed: Vec<char> = normalization::nfd(&c.to_string()).chars().collect();
+    let (base, mark) = match decomposed.as_slice() {
+        &[base, mark] => (base, mark),
+        _ => return None,
+    };
+    MACOS_DEAD_KEYS
+        .iter()
+        .find(|&&(_, m, letters)| m == mark && letters.contains(base))
+        .map(|&(key, _, _)| {
+            let letter = macos_key(base.to_ascii_uppercase(), base.is_ascii_uppercase());
+            format!("Option+{}, then {}", key, letter)
+        })
+}
+
+#[test]
+fn digraph_lookup() {
+    assert_eq!(digraphs('ä').collect::<Vec<_>>(), vec!["a:"]);
+    assert_eq!(digraphs('→').collect::<Vec<_>>(), vec!["->"]);
+    assert_eq!(digraphs('\u{1F600}').count(), 0);
+    assert_eq!(from_digraph("a:"), Some('ä'));
+    assert_eq!(from_digraph(":a"), Some('ä'));
+    assert_eq!(from_digraph("->"), Some('→'));
+    assert_eq!(from_digraph("Eu"), Some('€'));
+    assert_eq!(from_digraph("qq"), None);
+}
+
+#[test]
+fn typing_sequences() {
+    assert!(compose_sequences('≠').contains(&"= /"));
+    assert!(compose_sequences('é').contains(&"' e"));
+    assert!(compose_sequences('\u{1F600}').is_empty());
+    assert_eq!(gtk_sequence('≠'), "Ctrl+Shift+U, 2260, Space");
+    assert_eq!(windows_alt_x('é'), "00E9, Alt+X");
+    assert_eq!(
+        windows_alt_codes('é'),
+        vec!["Alt+0233 (CP1252)", "Alt+130 (CP437)"]
+    );
+    assert_eq!(windows_alt_codes('€'), vec!["Alt+0128 (CP1252)"]);
+    assert!(windows_alt_codes('≠').is_empty());
+    assert_eq!(macos_sequence('≠'), Some("Option+=".to_string()));
+    assert_eq!(macos_sequence('—'), Some("Option+Shift+-".to_string()));
+    assert_eq!(macos_sequence('é'), Some("Option+E, then E".to_string()));
+    assert_eq!(
+        macos_sequence('Ü'),
+        Some("Option+U, then Shift+U".to_string())
+    );
+    assert_eq!(macos_sequence('ǹ'), None);
+}
@@ -0,0 +1,54 @@
+//! Formal name aliases from NameAliases.txt, and the Unicode 1.0
+//! names from UnicodeData.txt.
+
+include!(concat!(env!("OUT_DIR"), "/unicode/aliases.rs"));
+include!(concat!(env!("OUT_DIR"), "/unicode/old_names.rs"));
+
+/// Returns the name aliases of `c` along with their type, in the order
+/// they are listed in NameAliases.txt.
+pub fn aliases(c: char) -> &'static [(char, &'static str, &'static str)] {
+    let start = NAME_ALIASES.partition_point(|&(ch, _, _)| ch < c);
+    let end = NAME_ALIASES.partition_point(|&(ch, _, _)| ch <= c);
+    &NAME_ALIASES[start..end]
+}
+
+/// Returns the name `c` had in Unicode 1.0, if it had one.
+pub fn unicode_1_name(c: char) -> Option<&'static str> {
+    UNICODE_1_NAMES
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| UNICODE_1_NAMES[i].1)
+}
+
+/// Describes an alias type the way it is shown next to a character.
+pub fn describe_alias_type(alias_type: &str) -> &str {
+    match alias_type {
+        "correction" => "Corrected name",
+        "control" => "Control name",
+        "alternate" => "Alternate name",
+        "figment" => "Figment name",
+        "abbreviation" => "Abbreviation",
+        other => other,
+    }
+}
+
+#[test]
+fn aliases_by_type() {
+    assert_eq!(
+        aliases('\u{feff}'),
+        &[
+            ('\u{feff}', "BYTE ORDER MARK", "alternate"),
+            ('\u{feff}', "BOM", "abbreviation"),
+            ('\u{feff}', "ZWNBSP", "abbreviation"),
+        ]
+    );
+    assert_eq!(aliases('\u{1a2}')[0].2, "correction");
+    assert!(aliases('a').is_empty());
+}
+
+#[test]
+fn unicode_1_names() {
+    assert_eq!(unicode_1_name('λ'), Some("GREEK SMALL LETTER LAMBDA"));
+    assert_eq!(unicode_1_name('\n'), Some("LINE FEED (LF)"));
+    assert_eq!(unicode_1_name('a'), None);
+}
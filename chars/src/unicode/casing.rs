@@ -0,0 +1,88 @@
+//! Case mappings beyond upper and lower case: title case, case
+//! folding, special casing rules and case equivalence classes.
+
+include!(concat!(env!("OUT_DIR"), "/unicode/casing.rs"));
+
+/// Returns the full title case mapping of `c`, if it differs from the
+/// upper case mapping.
+pub fn titlecase(c: char) -> Option<&'static str> {
+    TITLECASE
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| TITLECASE[i].1)
+}
+
+/// Returns the simple and the full case folding of `c`, if `c` isn't
+/// already case folded.
+pub fn case_folding(c: char) -> Option<(char, &'static str)> {
+    CASE_FOLDING
+        .binary_search_by_key(&c, |&(ch, _, _)| ch)
+        .ok()
+        .map(|i| (CASE_FOLDING[i].1, CASE_FOLDING[i].2))
+}
+
+/// Returns the case folding that Turkish and Azeri use for `c` instead
+/// of the default one.
+pub fn turkic_case_folding(c: char) -> Option<char> {
+    TURKIC_CASE_FOLDING
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| TURKIC_CASE_FOLDING[i].1)
+}
+
+/// Returns all characters that are equal to `c` under simple case
+/// folding (including `c` itself), in code point order.
+pub fn case_class(c: char) -> Vec<char> {
+    let folded = case_folding(c).map(|(simple, _)| simple).unwrap_or(c);
+    match CASE_CLASSES.binary_search_by_key(&folded, |&(ch, _)| ch) {
+        Ok(i) => CASE_CLASSES[i].1.chars().collect(),
+        Err(_) => vec![c],
+    }
+}
+
+/// Returns the language- or context-dependent case mappings of `c`.
+pub fn conditional_casings(c: char) -> impl Iterator<Item = &'static ConditionalCasing> {
+    CONDITIONAL_CASING
+        .iter()
+        .filter(move |casing| casing.c == c)
+}
+
+/// Whether a SpecialCasing.txt condition is a language code (as
+/// opposed to a casing context like `Final_Sigma`).
+pub fn is_language(condition: &str) -> bool {
+    condition.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Describes a SpecialCasing.txt condition (a language code or a
+/// casing context) in words.
+pub fn describe_condition(condition: &str) -> &str {
+    match condition {
+        "tr" => "Turkish",
+        "az" => "Azeri",
+        "lt" => "Lithuanian",
+        "Final_Sigma" => "at the end of a word",
+        "After_Soft_Dotted" => "after a soft-dotted letter like i",
+        "More_Above" => "before an accent above",
+        "Before_Dot" => "before COMBINING DOT ABOVE",
+        "Not_Before_Dot" => "unless before COMBINING DOT ABOVE",
+        "After_I" => "after I",
+        other => other,
+    }
+}
+
+#[test]
+fn case_classes() {
+    assert_eq!(case_class('K'), vec!['K', 'k', '\u{212a}']);
+    assert_eq!(case_class('\u{212a}'), vec!['K', 'k', '\u{212a}']);
+    assert_eq!(case_class('1'), vec!['1']);
+}
+
+#[test]
+fn title_and_folding() {
+    assert_eq!(titlecase('\u{1c6}'), Some("\u{1c5}"));
+    assert_eq!(titlecase('ß'), Some("Ss"));
+    assert_eq!(titlecase('a'), None);
+    assert_eq!(case_folding('ß'), Some(('ß', "ss")));
+    assert_eq!(case_folding('a'), None);
+    assert_eq!(turkic_case_folding('I'), Some('ı'));
+}
@@ -0,0 +1,62 @@
+//! Visually confusable characters and skeletons, as defined in
+//! [UTS #39](https://www.unicode.org/reports/tr39/#Confusable_Detection).
+
+use super::normalization;
+
+include!(concat!(env!("OUT_DIR"), "/unicode/confusables.rs"));
+
+/// Returns the prototype that `c` is confusable with, if it isn't a
+/// prototype itself.
+pub fn prototype(c: char) -> Option<&'static str> {
+    PROTOTYPES
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| PROTOTYPES[i].1)
+}
+
+/// Returns the skeleton of a string: two strings are confusable if
+/// their skeletons are identical.
+pub fn skeleton(s: &str) -> String {
+    let mut mapped = String::new();
+    for c in normalization::nfd(s).chars() {
+        match prototype(c) {
+            Some(proto) => mapped.push_str(proto),
+            None => mapped.push(c),
+        }
+    }
+    normalization::nfd(&mapped)
+}
+
+/// Returns all other characters that share a prototype with `c`, in
+/// code point order.
+pub fn lookalikes(c: char) -> Vec<char> {
+    let own = c.to_string();
+    let proto = prototype(c).unwrap_or(&own);
+    let mut chars: Vec<char> = vec![];
+    let mut proto_chars = proto.chars();
+    if let (Some(p), None) = (proto_chars.next(), proto_chars.next()) {
+        chars.push(p);
+    }
+    if let Ok(i) = CONFUSABLE_CLASSES.binary_search_by_key(&proto, |&(p, _)| p) {
+        chars.extend(CONFUSABLE_CLASSES[i].1.chars());
+    }
+    chars.retain(|&other| other != c);
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
+#[test]
+fn skeletons_match_for_confusables() {
+    assert_eq!(skeleton("p\u{430}yp\u{430}l"), skeleton("paypal"));
+    assert_ne!(skeleton("paypal"), skeleton("paypel"));
+    assert_eq!(prototype('\u{430}'), Some("a"));
+    assert_eq!(prototype('a'), None);
+}
+
+#[test]
+fn lookalikes_are_symmetric() {
+    assert!(lookalikes('a').contains(&'\u{430}'));
+    assert!(lookalikes('\u{430}').contains(&'a'));
+    assert!(!lookalikes('a').contains(&'a'));
+}
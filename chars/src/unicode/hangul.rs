@@ -0,0 +1,248 @@
+//! Hangul syllables and the conjoining jamo they are composed of,
+//! following the algorithm in section 3.12 of the Unicode standard.
+
+use super::normalization::{
+    L_BASE, L_COUNT, N_COUNT, S_BASE, S_COUNT, T_BASE, T_COUNT, V_BASE, V_COUNT,
+};
+
+/// The Jamo_Short_Name of the leading consonants (choseong), from
+/// Jamo.txt. IEUNG has an empty short name.
+const L_SHORT_NAMES: [&str; L_COUNT as usize] = [
+    "G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T", "P",
+    "H",
+];
+
+/// The Jamo_Short_Name of the vowels (jungseong).
+const V_SHORT_NAMES: [&str; V_COUNT as usize] = [
+    "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WEO", "WE",
+    "WI", "YU", "EU", "YI", "I",
+];
+
+/// The Jamo_Short_Name of the trailing consonants (jongseong); the
+/// first entry stands for "no trailing consonant".
+const T_SHORT_NAMES: [&str; T_COUNT as usize] = [
+    "", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP", "LH", "M",
+    "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H",
+];
+
+/// The Hangul_Syllable_Type property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyllableType {
+    LeadingJamo,
+    VowelJamo,
+    TrailingJamo,
+    LvSyllable,
+    LvtSyllable,
+}
+
+impl SyllableType {
+    /// The short property value alias, as in HangulSyllableType.txt.
+    pub fn short_name(self) -> &'static str {
+        match self {
+            SyllableType::LeadingJamo => "L",
+            SyllableType::VowelJamo => "V",
+            SyllableType::TrailingJamo => "T",
+            SyllableType::LvSyllable => "LV",
+            SyllableType::LvtSyllable => "LVT",
+        }
+    }
+
+    /// A description of the property value in words.
+    pub fn description(self) -> &'static str {
+        match self {
+            SyllableType::LeadingJamo => "leading consonant jamo (choseong)",
+            SyllableType::VowelJamo => "vowel jamo (jungseong)",
+            SyllableType::TrailingJamo => "trailing consonant jamo (jongseong)",
+            SyllableType::LvSyllable => "syllable without a trailing consonant",
+            SyllableType::LvtSyllable => "syllable with a trailing consonant",
+        }
+    }
+}
+
+/// Returns the Hangul_Syllable_Type of `c`, if it is a Hangul syllable
+/// or a conjoining jamo.
+pub fn syllable_type(c: char) -> Option<SyllableType> {
+    let s_index = (c as u32).wrapping_sub(S_BASE);
+    if s_index < S_COUNT {
+        return match s_index % T_COUNT {
+            0 => Some(SyllableType::LvSyllable),
+            _ => Some(SyllableType::LvtSyllable),
+        };
+    }
+    match c {
+        '\u{1100}'..='\u{115F}' | '\u{A960}'..='\u{A97C}' => Some(SyllableType::LeadingJamo),
+        '\u{1160}'..='\u{11A7}' | '\u{D7B0}'..='\u{D7C6}' => Some(SyllableType::VowelJamo),
+        '\u{11A8}'..='\u{11FF}' | '\u{D7CB}'..='\u{D7FB}' => Some(SyllableType::TrailingJamo),
+        _ => None,
+    }
+}
+
+/// Returns the Jamo_Short_Name of a conjoining jamo that takes part in
+/// composing the precomposed syllables.
+pub fn short_name(jamo: char) -> Option<&'static str> {
+    let cp = jamo as u32;
+    if (L_BASE..L_BASE + L_COUNT).contains(&cp) {
+        Some(L_SHORT_NAMES[(cp - L_BASE) as usize])
+    } else if (V_BASE..V_BASE + V_COUNT).contains(&cp) {
+        Some(V_SHORT_NAMES[(cp - V_BASE) as usize])
+    } else if (T_BASE + 1..T_BASE + T_COUNT).contains(&cp) {
+        Some(T_SHORT_NAMES[(cp - T_BASE) as usize])
+    } else {
+        None
+    }
+}
+
+/// Splits a precomposed Hangul syllable into its leading consonant,
+/// vowel and (if it has one) trailing consonant.
+pub fn decompose(c: char) -> Option<(char, char, Option<char>)> {
+    let s_index = (c as u32).wrapping_sub(S_BASE);
+    if s_index >= S_COUNT {
+        return None;
+    }
+    let l = std::char::from_u32(L_BASE + s_index / N_COUNT)?;
+    let v = std::char::from_u32(V_BASE + (s_index % N_COUNT) / T_COUNT)?;
+    let t = match s_index % T_COUNT {
+        0 => None,
+        t_index => std::char::from_u32(T_BASE + t_index),
+    };
+    Some((l, v, t))
+}
+
+/// Composes a Hangul syllable from a leading consonant, a vowel and an
+/// optional trailing consonant.
+pub fn compose(l: char, v: char, t: Option<char>) -> Option<char> {
+    let l_index = (l as u32).wrapping_sub(L_BASE);
+    let v_index = (v as u32).wrapping_sub(V_BASE);
+    let t_index = match t {
+        None => 0,
+        Some(t) => match (t as u32).wrapping_sub(T_BASE) {
+            0 => return None,
+            t_index => t_index,
+        },
+    };
+    if l_index >= L_COUNT || v_index >= V_COUNT || t_index >= T_COUNT {
+        return None;
+    }
+    std::char::from_u32(S_BASE + (l_index * V_COUNT + v_index) * T_COUNT + t_index)
+}
+
+/// Composes a syllable from a string of two or three conjoining jamo.
+pub fn compose_jamo(jamo: &str) -> Option<char> {
+    let mut chars = jamo.chars();
+    let l = chars.next()?;
+    let v = chars.next()?;
+    let t = chars.next();
+    if chars.next().is_some() {
+        return None;
+    }
+    compose(l, v, t)
+}
+
+/// Returns all syllables whose jamo short names spell out
+/// `romanized`, like "HAN" for 한 or "AN" for 안.
+pub fn from_romanization(romanized: &str) -> Vec<char> {
+    let romanized = romanized.to_uppercase();
+    let mut syllables = vec![];
+    for (l_index, l) in L_SHORT_NAMES.iter().enumerate() {
+        let rest = match romanized.strip_prefix(l) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        for (v_index, v) in V_SHORT_NAMES.iter().enumerate() {
+            let rest = match rest.strip_prefix(v) {
+                Some(rest) => rest,
+                None => continue,
+            };
+            if let Some(t_index) = T_SHORT_NAMES.iter().position(|t| *t == rest) {
+                let index = (l_index as u32 * V_COUNT + v_index as u32) * T_COUNT + t_index as u32;
+                syllables.extend(std::char::from_u32(S_BASE + index));
+            }
+        }
+    }
+    syllables
+}
+
+/// Resolves a single jamo at position `index` (leading consonant,
+/// vowel, trailing consonant) of a syllable from either the jamo
+/// itself, its short name or its character name.
+fn resolve_jamo(spec: &str, index: usize) -> Option<char> {
+    let (base, names, wanted): (u32, &[&str], _) = match index {
+        0 => (L_BASE, &L_SHORT_NAMES, SyllableType::LeadingJamo),
+        1 => (V_BASE, &V_SHORT_NAMES, SyllableType::VowelJamo),
+        2 => (T_BASE, &T_SHORT_NAMES, SyllableType::TrailingJamo),
+        _ => return None,
+    };
+    let spec = spec.trim();
+    if spec.chars().count() == 1 {
+        let c = spec.chars().next()?;
+        if syllable_type(c) == Some(wanted) {
+            return Some(c);
+        }
+    }
+    if let Some(i) = names
+        .iter()
+        .position(|name| !name.is_empty() && name.eq_ignore_ascii_case(spec))
+    {
+        return std::char::from_u32(base + i as u32);
+    }
+    super::lookup_by_query(spec)
+        .into_iter()
+        .find(|&c| syllable_type(c) == Some(wanted) && short_name(c).is_some())
+}
+
+/// Composes the syllables that a `+`-separated list of jamo (given as
+/// the jamo themselves, their short names or their names) or a single
+/// romanized syllable stands for.
+pub fn from_spec(spec: &str) -> Vec<char> {
+    if !spec.contains('+') {
+        if let Some(syllable) = compose_jamo(spec) {
+            return vec![syllable];
+        }
+        return from_romanization(spec);
+    }
+    let parts: Vec<&str> = spec.split('+').collect();
+    if parts.len() > 3 {
+        return vec![];
+    }
+    let jamo: Option<Vec<char>> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| resolve_jamo(part, i))
+        .collect();
+    match jamo.as_deref() {
+        Some(&[l, v]) => compose(l, v, None).into_iter().collect(),
+        Some(&[l, v, t]) => compose(l, v, Some(t)).into_iter().collect(),
+        _ => vec![],
+    }
+}
+
+#[test]
+fn decomposition() {
+    assert_eq!(
+        decompose('한'),
+        Some(('\u{1112}', '\u{1161}', Some('\u{11ab}')))
+    );
+    assert_eq!(decompose('가'), Some(('\u{1100}', '\u{1161}', None)));
+    assert_eq!(decompose('a'), None);
+    assert_eq!(short_name('\u{1112}'), Some("H"));
+    assert_eq!(short_name('\u{11ab}'), Some("N"));
+    assert_eq!(short_name('\u{110b}'), Some(""));
+    assert_eq!(syllable_type('한'), Some(SyllableType::LvtSyllable));
+    assert_eq!(syllable_type('가'), Some(SyllableType::LvSyllable));
+    assert_eq!(syllable_type('\u{a960}'), Some(SyllableType::LeadingJamo));
+    assert_eq!(syllable_type('a'), None);
+}
+
+#[test]
+fn composition() {
+    assert_eq!(compose_jamo("\u{1112}\u{1161}\u{11ab}"), Some('한'));
+    assert_eq!(compose_jamo("\u{1100}\u{1161}"), Some('가'));
+    assert_eq!(compose_jamo("ab"), None);
+    assert_eq!(from_romanization("han"), vec!['한']);
+    assert_eq!(from_romanization("an"), vec!['안']);
+    assert_eq!(from_romanization("xyz"), vec![]);
+    assert_eq!(from_spec("h+a+n"), vec!['한']);
+    assert_eq!(from_spec("\u{1112}+a+n"), vec!['한']);
+    assert_eq!(from_spec("g+a"), vec!['가']);
+    assert_eq!(from_spec("g+x"), vec![]);
+}
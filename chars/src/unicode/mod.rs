@@ -3,6 +3,16 @@ use std::collections::BTreeSet;
 
 use fst::Map;
 
+pub mod aliases;
+pub mod casing;
+pub mod confusables;
+pub mod hangul;
+pub mod names_list;
+pub mod normalization;
+pub mod properties;
+pub mod surrogates;
+pub mod unihan;
+
 const BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/unicode/name_fst.bin"));
 include!(concat!(env!("OUT_DIR"), "/unicode/names.rs"));
 
@@ -58,3 +68,33 @@ pub fn lookup_by_query(query: &str) -> Vec<char> {
     }
     candidates.into_iter().collect()
 }
+
+/// Finds ideographs by the words of their English definition, with or
+/// without a `definition:` prefix. Several words must all be in the
+/// definition, in any order.
+pub fn lookup_by_definition(query: &str) -> Vec<char> {
+    let query = query.to_lowercase();
+    let query = query.strip_prefix("definition:").unwrap_or(&query);
+    let phrase_results = query_fst(&format!("definition:{}", query));
+    if !phrase_results.is_empty() || !query.contains(char::is_whitespace) {
+        return phrase_results;
+    }
+
+    let mut words = query.split_whitespace();
+    let mut candidates: BTreeSet<char> = match words.next() {
+        Some(word) => query_fst(&format!("definition:{}", word))
+            .into_iter()
+            .collect(),
+        None => return vec![],
+    };
+    for word in words {
+        let merge_candidates: BTreeSet<char> = query_fst(&format!("definition:{}", word))
+            .into_iter()
+            .collect();
+        candidates = candidates
+            .intersection(&merge_candidates)
+            .cloned()
+            .collect();
+    }
+    candidates.into_iter().collect()
+}
@@ -0,0 +1,37 @@
+//! Editorial annotations from the Unicode code charts (NamesList.txt):
+//! informative aliases, notes, cross references and subheaders.
+
+include!(concat!(env!("OUT_DIR"), "/unicode/names_list.rs"));
+
+/// Returns the informative aliases, notes and cross references of `c`,
+/// if the code charts have any.
+pub fn annotations(c: char) -> Option<&'static Annotations> {
+    ANNOTATIONS
+        .binary_search_by_key(&c, |annotations| annotations.c)
+        .ok()
+        .map(|i| &ANNOTATIONS[i])
+}
+
+/// Returns the subheader that `c` is listed under in the code charts.
+pub fn subheader(c: char) -> Option<&'static str> {
+    let i = match SUBHEADERS.binary_search_by_key(&c, |&(start, _, _)| start) {
+        Ok(i) => i,
+        Err(0) => return None,
+        Err(i) => i - 1,
+    };
+    let (_, end, name) = SUBHEADERS[i];
+    if c <= end {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn hyphen_minus() {
+    let hyphen_minus = annotations('-').unwrap();
+    assert_eq!(hyphen_minus.aliases, &["hyphen or minus sign"]);
+    assert_eq!(hyphen_minus.see_also, &['\u{2010}', '\u{2212}']);
+    assert_eq!(subheader('-'), Some("ASCII punctuation and symbols"));
+    assert!(annotations('A').is_none());
+}
@@ -0,0 +1,243 @@
+//! Unicode normalization, backed by the decomposition tables that
+//! chars_data generates from UnicodeData.txt.
+
+include!(concat!(env!("OUT_DIR"), "/unicode/normalization.rs"));
+
+// Constants for the algorithmic decomposition of Hangul syllables,
+// from section 3.12 of the Unicode standard.
+pub const S_BASE: u32 = 0xAC00;
+pub const L_BASE: u32 = 0x1100;
+pub const V_BASE: u32 = 0x1161;
+pub const T_BASE: u32 = 0x11A7;
+pub const L_COUNT: u32 = 19;
+pub const V_COUNT: u32 = 21;
+pub const T_COUNT: u32 = 28;
+pub const N_COUNT: u32 = V_COUNT * T_COUNT;
+pub const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+/// Returns the canonical combining class of a character; 0 for
+/// starters.
+pub fn combining_class(c: char) -> u8 {
+    COMBINING_CLASSES
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .map(|i| COMBINING_CLASSES[i].1)
+        .unwrap_or(0)
+}
+
+fn decompose_hangul(c: char, out: &mut Vec<char>) -> bool {
+    let s_index = (c as u32).wrapping_sub(S_BASE);
+    if s_index >= S_COUNT {
+        return false;
+    }
+    let l = L_BASE + s_index / N_COUNT;
+    let v = V_BASE + (s_index % N_COUNT) / T_COUNT;
+    let t = T_BASE + s_index % T_COUNT;
+    out.extend(std::char::from_u32(l));
+    out.extend(std::char::from_u32(v));
+    if t != T_BASE {
+        out.extend(std::char::from_u32(t));
+    }
+    true
+}
+
+fn compose_hangul(first: char, second: char) -> Option<char> {
+    let (first, second) = (first as u32, second as u32);
+    let l_index = first.wrapping_sub(L_BASE);
+    let v_index = second.wrapping_sub(V_BASE);
+    if l_index < L_COUNT && v_index < V_COUNT {
+        return std::char::from_u32(S_BASE + (l_index * V_COUNT + v_index) * T_COUNT);
+    }
+    let s_index = first.wrapping_sub(S_BASE);
+    let t_index = second.wrapping_sub(T_BASE);
+    if s_index < S_COUNT && s_index % T_COUNT == 0 && t_index > 0 && t_index < T_COUNT {
+        return std::char::from_u32(first + t_index);
+    }
+    None
+}
+
+fn lookup(table: &'static [(char, &'static str)], c: char) -> Option<&'static str> {
+    table
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| table[i].1)
+}
+
+/// Appends the full decomposition of `c` to `out`, using compatibility
+/// mappings if `compatibility` is set. The parts of a mapping are
+/// decomposed again, as they can be Hangul syllables.
+fn decompose(c: char, compatibility: bool, out: &mut Vec<char>) {
+    if decompose_hangul(c, out) {
+        return;
+    }
+    let compat = if compatibility {
+        lookup(COMPATIBILITY_DECOMPOSITIONS, c)
+    } else {
+        None
+    };
+    match compat.or_else(|| lookup(CANONICAL_DECOMPOSITIONS, c)) {
+        Some(decomposed) => {
+            for part in decomposed.chars() {
+                decompose(part, compatibility, out);
+            }
+        }
+        None => out.push(c),
+    }
+}
+
+/// Returns the primary composite of two characters, if there is one.
+fn compose_pair(first: char, second: char) -> Option<char> {
+    if let Some(composed) = compose_hangul(first, second) {
+        return Some(composed);
+    }
+    COMPOSITIONS
+        .binary_search_by_key(&(first, second), |&(pair, _)| pair)
+        .ok()
+        .map(|i| COMPOSITIONS[i].1)
+}
+
+/// The canonical composition algorithm: combines each character with
+/// the last starter before it, unless something in between blocks it.
+fn compose(chars: Vec<char>) -> Vec<char> {
+    let mut composed: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter: Option<usize> = None;
+    let mut last_class: Option<u8> = None;
+    for c in chars {
+        let class = combining_class(c);
+        if let Some(starter) = starter {
+            let blocked = match last_class {
+                None => false,
+                Some(last) => last == 0 || last >= class,
+            };
+            if !blocked {
+                if let Some(pair) = compose_pair(composed[starter], c) {
+                    composed[starter] = pair;
+                    continue;
+                }
+            }
+        }
+        if class == 0 {
+            starter = Some(composed.len());
+            last_class = None;
+        } else {
+            last_class = Some(class);
+        }
+        composed.push(c);
+    }
+    composed
+}
+
+/// Puts runs of combining marks into canonical order, sorting them by
+/// their combining class (stably, so marks of the same class keep
+/// their relative order).
+fn canonical_order(chars: &mut [char]) {
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end < chars.len() && combining_class(chars[end]) != 0 {
+            end += 1;
+        }
+        chars[start..end].sort_by_key(|&c| combining_class(c));
+        start = end;
+    }
+}
+
+fn decompose_str(s: &str, compatibility: bool) -> Vec<char> {
+    let mut chars: Vec<char> = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        decompose(c, compatibility, &mut chars);
+    }
+    canonical_order(&mut chars);
+    chars
+}
+
+/// Returns the Normalization Form D (canonical decomposition) of `s`.
+pub fn nfd(s: &str) -> String {
+    decompose_str(s, false).into_iter().collect()
+}
+
+/// Returns the Normalization Form KD (compatibility decomposition) of
+/// `s`.
+pub fn nfkd(s: &str) -> String {
+    decompose_str(s, true).into_iter().collect()
+}
+
+/// Returns the Normalization Form C (canonical decomposition, followed
+/// by canonical composition) of `s`.
+pub fn nfc(s: &str) -> String {
+    compose(decompose_str(s, false)).into_iter().collect()
+}
+
+/// Returns the Normalization Form KC (compatibility decomposition,
+/// followed by canonical composition) of `s`.
+pub fn nfkc(s: &str) -> String {
+    compose(decompose_str(s, true)).into_iter().collect()
+}
+
+/// Returns the reason why `c` can't occur in NFC, if it has the
+/// Full_Composition_Exclusion property.
+pub fn composition_exclusion(c: char) -> Option<Exclusion> {
+    COMPOSITION_EXCLUSIONS
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| COMPOSITION_EXCLUSIONS[i].1)
+}
+
+/// Returns the NFC_Quick_Check and NFKC_Quick_Check properties of `c`.
+pub fn quick_check(c: char) -> (QuickCheck, QuickCheck) {
+    match QUICK_CHECKS.binary_search_by_key(&c, |&(ch, _, _)| ch) {
+        Ok(i) => (QUICK_CHECKS[i].1, QUICK_CHECKS[i].2),
+        Err(_) => (QuickCheck::Yes, QuickCheck::Yes),
+    }
+}
+
+#[test]
+fn nfd_decomposes() {
+    assert_eq!(nfd("abc"), "abc");
+    assert_eq!(nfd("é"), "e\u{301}");
+    assert_eq!(nfd("\u{1eaa}"), "A\u{302}\u{303}");
+    // Hangul syllables decompose algorithmically:
+    assert_eq!(nfd("한"), "\u{1112}\u{1161}\u{11ab}");
+    assert_eq!(nfd("가"), "\u{1100}\u{1161}");
+    // Combining marks get reordered, dot below (220) before acute (230):
+    assert_eq!(nfd("e\u{301}\u{323}"), "e\u{323}\u{301}");
+}
+
+#[test]
+fn normalization_forms() {
+    assert_eq!(nfc("e\u{301}"), "é");
+    assert_eq!(nfc("\u{1112}\u{1161}\u{11ab}"), "한");
+    assert_eq!(nfc("\u{1e0b}\u{323}"), "\u{1e0d}\u{307}");
+    // Singletons don't come back:
+    assert_eq!(nfc("\u{212b}"), "\u{c5}");
+    assert_eq!(nfkd("\u{fb01}"), "fi");
+    assert_eq!(nfkc("\u{2460}"), "1");
+    assert_eq!(nfd("\u{fb01}"), "\u{fb01}");
+    // Hangul syllables in compatibility mappings decompose too:
+    assert_eq!(nfkd("\u{320e}"), "(\u{1100}\u{1161})");
+    assert_eq!(nfkc("\u{320e}"), "(가)");
+}
+
+#[test]
+fn normalization_properties() {
+    assert_eq!(composition_exclusion('\u{958}'), Some(Exclusion::Listed));
+    assert_eq!(
+        composition_exclusion('\u{212b}'),
+        Some(Exclusion::Singleton)
+    );
+    assert_eq!(
+        composition_exclusion('\u{344}'),
+        Some(Exclusion::NonStarter)
+    );
+    assert_eq!(composition_exclusion('é'), None);
+    assert_eq!(quick_check('a'), (QuickCheck::Yes, QuickCheck::Yes));
+    assert_eq!(
+        quick_check('\u{301}'),
+        (QuickCheck::Maybe, QuickCheck::Maybe)
+    );
+    assert_eq!(quick_check('\u{fb01}'), (QuickCheck::Yes, QuickCheck::No));
+    assert_eq!(quick_check('\u{212b}'), (QuickCheck::No, QuickCheck::No));
+}
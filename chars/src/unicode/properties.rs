@@ -0,0 +1,187 @@
+//! Enumerated character properties from the UCD.
+
+include!(concat!(env!("OUT_DIR"), "/unicode/properties.rs"));
+
+fn lookup_range(table: &'static [(char, char, &'static str)], c: char) -> Option<&'static str> {
+    let i = match table.binary_search_by_key(&c, |&(start, _, _)| start) {
+        Ok(i) => i,
+        Err(0) => return None,
+        Err(i) => i - 1,
+    };
+    let (_, end, value) = table[i];
+    if c <= end {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Looks up the default value of a property for a code point that
+/// isn't listed, from the file's `@missing` lines. Later lines
+/// override earlier ones.
+fn lookup_missing(table: &'static [(char, char, &'static str)], c: char) -> Option<&'static str> {
+    table
+        .iter()
+        .rev()
+        .find(|&&(start, end, _)| start <= c && c <= end)
+        .map(|&(_, _, value)| value)
+}
+
+/// Returns the East_Asian_Width property value of `c`. Unlisted code
+/// points get the defaults the file states: W in the CJK ideograph
+/// blocks and planes, and N everywhere else.
+pub fn east_asian_width(c: char) -> &'static str {
+    lookup_range(EAST_ASIAN_WIDTH, c)
+        .or_else(|| lookup_missing(EAST_ASIAN_WIDTH_MISSING, c))
+        .unwrap_or("N")
+}
+
+/// Returns the Line_Break property value of `c`. Unlisted code points
+/// get the defaults the file states, like ID in the CJK ideograph
+/// blocks and planes, and XX everywhere else.
+pub fn line_break(c: char) -> &'static str {
+    lookup_range(LINE_BREAK, c)
+        .or_else(|| lookup_missing(LINE_BREAK_MISSING, c))
+        .unwrap_or("XX")
+}
+
+/// Returns the General_Category of `c` (Cn, unassigned, if it isn't
+/// listed).
+pub fn general_category(c: char) -> &'static str {
+    lookup_range(GENERAL_CATEGORY, c).unwrap_or("Cn")
+}
+
+/// Whether `c` has the Default_Ignorable_Code_Point property.
+pub fn is_default_ignorable(c: char) -> bool {
+    lookup_range(DEFAULT_IGNORABLE, c).is_some()
+}
+
+/// Whether `c` is one of the 66 noncharacters: U+FDD0..U+FDEF and the
+/// last two code points of every plane.
+pub fn is_noncharacter(c: char) -> bool {
+    let cp = c as u32;
+    (0xFDD0..=0xFDEF).contains(&cp) || cp & 0xFFFE == 0xFFFE
+}
+
+/// Whether `c` is in one of the private use areas.
+pub fn is_private_use(c: char) -> bool {
+    general_category(c) == "Co"
+}
+
+/// Returns the number, name and abbreviation of the plane that `c` is
+/// in.
+pub fn plane(c: char) -> (u32, &'static str, Option<&'static str>) {
+    let plane = c as u32 >> 16;
+    let (name, abbreviation) = match plane {
+        0 => ("Basic Multilingual Plane", Some("BMP")),
+        1 => ("Supplementary Multilingual Plane", Some("SMP")),
+        2 => ("Supplementary Ideographic Plane", Some("SIP")),
+        3 => ("Tertiary Ideographic Plane", Some("TIP")),
+        14 => ("Supplementary Special-purpose Plane", Some("SSP")),
+        15 => ("Supplementary Private Use Area-A", Some("PUA-A")),
+        16 => ("Supplementary Private Use Area-B", Some("PUA-B")),
+        _ => ("Unassigned plane", None),
+    };
+    (plane, name, abbreviation)
+}
+
+/// Returns the long name of an East_Asian_Width value.
+pub fn east_asian_width_name(value: &str) -> &str {
+    match value {
+        "A" => "Ambiguous",
+        "F" => "Fullwidth",
+        "H" => "Halfwidth",
+        "N" => "Neutral",
+        "Na" => "Narrow",
+        "W" => "Wide",
+        other => other,
+    }
+}
+
+/// Returns the long name of a Line_Break class, as given in UAX #14.
+pub fn line_break_name(value: &str) -> &str {
+    match value {
+        "AI" => "Ambiguous (Alphabetic or Ideographic)",
+        "AK" => "Aksara",
+        "AL" => "Alphabetic",
+        "AP" => "Aksara Prebase",
+        "AS" => "Aksara Start",
+        "B2" => "Break Opportunity Before and After",
+        "BA" => "Break After",
+        "BB" => "Break Before",
+        "BK" => "Mandatory Break",
+        "CB" => "Contingent Break Opportunity",
+        "CJ" => "Conditional Japanese Starter",
+        "CL" => "Close Punctuation",
+        "CM" => "Combining Mark",
+        "CP" => "Close Parenthesis",
+        "CR" => "Carriage Return",
+        "EB" => "Emoji Base",
+        "EM" => "Emoji Modifier",
+        "EX" => "Exclamation/Interrogation",
+        "GL" => "Non-breaking (\"Glue\")",
+        "H2" => "Hangul LV Syllable",
+        "H3" => "Hangul LVT Syllable",
+        "HL" => "Hebrew Letter",
+        "HY" => "Hyphen",
+        "ID" => "Ideographic",
+        "IN" => "Inseparable",
+        "IS" => "Infix Numeric Separator",
+        "JL" => "Hangul L Jamo",
+        "JT" => "Hangul T Jamo",
+        "JV" => "Hangul V Jamo",
+        "LF" => "Line Feed",
+        "NL" => "Next Line",
+        "NS" => "Nonstarter",
+        "NU" => "Numeric",
+        "OP" => "Open Punctuation",
+        "PO" => "Postfix Numeric",
+        "PR" => "Prefix Numeric",
+        "QU" => "Quotation",
+        "RI" => "Regional Indicator",
+        "SA" => "Complex Context Dependent (South East Asian)",
+        "SG" => "Surrogate",
+        "SP" => "Space",
+        "SY" => "Symbols Allowing Break After",
+        "VF" => "Virama Final",
+        "VI" => "Virama",
+        "WJ" => "Word Joiner",
+        "XX" => "Unknown",
+        "ZW" => "Zero Width Space",
+        "ZWJ" => "Zero Width Joiner",
+        other => other,
+    }
+}
+
+#[test]
+fn property_lookups() {
+    assert_eq!(east_asian_width('a'), "Na");
+    assert_eq!(east_asian_width('\u{a1}'), "A");
+    assert_eq!(east_asian_width('漢'), "W");
+    assert_eq!(line_break('a'), "AL");
+    assert_eq!(line_break('\n'), "LF");
+    assert_eq!(line_break('\u{a0}'), "GL");
+    // Unassigned code points get the defaults of their block:
+    assert_eq!(east_asian_width('\u{2a6e0}'), "W");
+    assert_eq!(line_break('\u{2a6e0}'), "ID");
+    assert_eq!(east_asian_width('\u{378}'), "N");
+    assert_eq!(line_break('\u{378}'), "XX");
+}
+
+#[test]
+fn classification() {
+    assert_eq!(general_category('A'), "Lu");
+    assert_eq!(general_category('한'), "Lo");
+    assert_eq!(general_category('\u{378}'), "Cn");
+    assert!(is_private_use('\u{e000}'));
+    assert!(is_private_use('\u{10fffd}'));
+    assert!(!is_private_use('\u{10ffff}'));
+    assert!(is_noncharacter('\u{fdd0}'));
+    assert!(is_noncharacter('\u{1fffe}'));
+    assert!(!is_noncharacter('\u{fffd}'));
+    assert!(is_default_ignorable('\u{200b}'));
+    assert!(!is_default_ignorable(' '));
+    assert_eq!(plane('a').2, Some("BMP"));
+    assert_eq!(plane('\u{1f600}').2, Some("SMP"));
+    assert_eq!(plane('\u{50000}'), (5, "Unassigned plane", None));
+}
@@ -0,0 +1,89 @@
+//! Surrogate code points (U+D800..U+DFFF): the halves of UTF-16
+//! surrogate pairs, which are code points but not Unicode scalar
+//! values, so they can't be a `char`.
+
+use std::ops::RangeInclusive;
+
+pub const HIGH_SURROGATES: RangeInclusive<u32> = 0xD800..=0xDBFF;
+pub const LOW_SURROGATES: RangeInclusive<u32> = 0xDC00..=0xDFFF;
+
+/// Whether `cp` is a surrogate code point.
+pub fn is_surrogate(cp: u32) -> bool {
+    HIGH_SURROGATES.contains(&cp) || LOW_SURROGATES.contains(&cp)
+}
+
+/// Returns the name of the block a surrogate is in.
+pub fn block_name(cp: u32) -> &'static str {
+    match cp {
+        0xD800..=0xDB7F => "High Surrogates",
+        0xDB80..=0xDBFF => "High Private Use Surrogates",
+        _ => "Low Surrogates",
+    }
+}
+
+/// Combines a high and a low surrogate into the supplementary scalar
+/// value they encode in UTF-16.
+pub fn combine(high: u32, low: u32) -> Option<char> {
+    if !HIGH_SURROGATES.contains(&high) || !LOW_SURROGATES.contains(&low) {
+        return None;
+    }
+    std::char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+}
+
+/// Returns the range of supplementary scalar values that a high
+/// surrogate starts, or that a low surrogate can end.
+pub fn combined_range(cp: u32) -> Option<(char, char)> {
+    if HIGH_SURROGATES.contains(&cp) {
+        Some((combine(cp, 0xDC00)?, combine(cp, 0xDFFF)?))
+    } else if LOW_SURROGATES.contains(&cp) {
+        Some((combine(0xD800, cp)?, combine(0xDBFF, cp)?))
+    } else {
+        None
+    }
+}
+
+/// Encodes a code point in three bytes the way UTF-8 would if it
+/// allowed surrogates. This is how WTF-8 and CESU-8 (and Java's
+/// Modified UTF-8) represent a lone surrogate.
+pub fn encode_generalized_utf8(cp: u32) -> Vec<u8> {
+    vec![
+        0xE0 | (cp >> 12) as u8,
+        0x80 | ((cp >> 6) & 0x3F) as u8,
+        0x80 | (cp & 0x3F) as u8,
+    ]
+}
+
+/// Encodes a character in CESU-8, where supplementary characters are
+/// encoded as the generalized UTF-8 of their two UTF-16 surrogates.
+pub fn encode_cesu8(c: char) -> Vec<u8> {
+    let mut units = [0; 2];
+    let units = c.encode_utf16(&mut units);
+    if units.len() == 1 {
+        let mut buf = [0; 4];
+        return c.encode_utf8(&mut buf).as_bytes().to_vec();
+    }
+    units
+        .iter()
+        .flat_map(|&unit| encode_generalized_utf8(u32::from(unit)))
+        .collect()
+}
+
+#[test]
+fn surrogate_pairs() {
+    assert_eq!(combine(0xD83D, 0xDE00), Some('\u{1f600}'));
+    assert_eq!(combine(0xDE00, 0xD83D), None);
+    assert_eq!(combined_range(0xD800), Some(('\u{10000}', '\u{103ff}')));
+    assert!(is_surrogate(0xDFFF));
+    assert!(!is_surrogate(0xE000));
+    assert_eq!(block_name(0xDB80), "High Private Use Surrogates");
+}
+
+#[test]
+fn encodings() {
+    assert_eq!(encode_generalized_utf8(0xD83D), vec![0xed, 0xa0, 0xbd]);
+    assert_eq!(
+        encode_cesu8('\u{1f600}'),
+        vec![0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80]
+    );
+    assert_eq!(encode_cesu8('é'), vec![0xc3, 0xa9]);
+}
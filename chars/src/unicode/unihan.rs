@@ -0,0 +1,157 @@
+//! Readings, definitions, radical-stroke indexes and variants of CJK
+//! ideographs from the Unihan database.
+
+include!(concat!(env!("OUT_DIR"), "/unicode/unihan.rs"));
+
+/// Returns the definition and readings of an ideograph.
+pub fn readings(c: char) -> Option<&'static Readings> {
+    READINGS
+        .binary_search_by_key(&c, |readings| readings.c)
+        .ok()
+        .map(|i| &READINGS[i])
+}
+
+/// Drops the source annotations (like `:0E`) from kHangul readings.
+pub fn hangul_readings(khangul: &str) -> Vec<&str> {
+    khangul
+        .split_whitespace()
+        .map(|reading| reading.split(':').next().unwrap_or(reading))
+        .collect()
+}
+
+/// Returns the radical-stroke indexes of an ideograph.
+pub fn radical_strokes(c: char) -> &'static [RadicalStroke] {
+    let start = RADICAL_STROKES.partition_point(|rs| rs.c < c);
+    let end = RADICAL_STROKES.partition_point(|rs| rs.c <= c);
+    &RADICAL_STROKES[start..end]
+}
+
+impl RadicalForm {
+    /// The mark that follows the radical number in this form.
+    pub fn marker(self) -> &'static str {
+        match self {
+            RadicalForm::Traditional => "",
+            RadicalForm::Simplified => "'",
+            RadicalForm::NonChineseSimplified => "''",
+        }
+    }
+}
+
+/// Returns the Kangxi radical with the given number (or one of its
+/// simplified forms).
+pub fn radical(number: u8, form: RadicalForm) -> Option<&'static CjkRadical> {
+    CJK_RADICALS
+        .binary_search_by_key(&(number, form), |radical| (radical.number, radical.form))
+        .ok()
+        .map(|i| &CJK_RADICALS[i])
+}
+
+/// Returns the characters in the CJK Radicals Supplement block that
+/// are variant forms of a character from the Kangxi Radicals block
+/// (like CJK RADICAL WATER ONE for KANGXI RADICAL WATER).
+pub fn supplement_forms(kangxi: char) -> Vec<char> {
+    let name = match unicode_names2::name(kangxi) {
+        Some(name) => name.to_string(),
+        None => return vec![],
+    };
+    let base = match name.strip_prefix("KANGXI RADICAL ") {
+        Some(base) => base,
+        None => return vec![],
+    };
+    (0x2e80..=0x2eff)
+        .filter_map(std::char::from_u32)
+        .filter(|&c| match unicode_names2::name(c) {
+            Some(name) => {
+                let name = name.to_string();
+                match name.strip_prefix("CJK RADICAL ") {
+                    Some(form) => form == base || form.starts_with(&format!("{} ", base)),
+                    None => false,
+                }
+            }
+            None => false,
+        })
+        .collect()
+}
+
+/// Returns the variants of an ideograph, as pairs of the Unihan field
+/// (like `kSimplifiedVariant`) and the variant.
+pub fn variants(c: char) -> impl Iterator<Item = (&'static str, char)> {
+    let start = VARIANTS.partition_point(|&(ch, _, _)| ch < c);
+    let end = VARIANTS.partition_point(|&(ch, _, _)| ch <= c);
+    VARIANTS[start..end]
+        .iter()
+        .map(|&(_, field, variant)| (field, variant))
+}
+
+/// Returns the variants of an ideograph of one kind.
+pub fn variants_of_kind(c: char, field: &str) -> Vec<char> {
+    variants(c)
+        .filter(|&(f, _)| f == field)
+        .map(|(_, variant)| variant)
+        .collect()
+}
+
+/// Describes a Unihan variant field in words.
+pub fn describe_variant_field(field: &str) -> &str {
+    match field {
+        "kSimplifiedVariant" => "Simplified variant",
+        "kTraditionalVariant" => "Traditional variant",
+        "kSemanticVariant" => "Semantic variant",
+        "kZVariant" => "Z-variant",
+        "kCompatibilityVariant" => "Compatibility variant",
+        other => other,
+    }
+}
+
+/// Converts an ideograph into its simplified or traditional form
+/// (depending on `field`). Returns the character it converts to, and
+/// the alternatives that were not picked. Characters that are listed as
+/// a variant of themselves are kept, as the mapping is ambiguous.
+pub fn convert_char(c: char, field: &str) -> (char, Vec<char>) {
+    let candidates = variants_of_kind(c, field);
+    let picked = if candidates.contains(&c) {
+        c
+    } else {
+        candidates.first().cloned().unwrap_or(c)
+    };
+    let others = candidates.into_iter().filter(|&v| v != picked).collect();
+    (picked, others)
+}
+
+/// Converts a string between simplified and traditional forms,
+/// character by character.
+pub fn convert(s: &str, field: &str) -> String {
+    s.chars().map(|c| convert_char(c, field).0).collect()
+}
+
+#[test]
+fn simplified_and_traditional() {
+    assert_eq!(convert("漢字", "kSimplifiedVariant"), "汉字");
+    assert_eq!(convert("汉字", "kTraditionalVariant"), "漢字");
+    assert_eq!(convert_char('乾', "kSimplifiedVariant"), ('乾', vec!['干']));
+    assert_eq!(variants_of_kind('綠', "kZVariant"), vec!['緑']);
+}
+
+#[test]
+fn radicals_of_han() {
+    let rs = radical_strokes('漢');
+    assert_eq!(rs.len(), 1);
+    assert_eq!(
+        (rs[0].radical, rs[0].form, rs[0].strokes),
+        (85, RadicalForm::Traditional, 11)
+    );
+    let water = radical(85, RadicalForm::Traditional).unwrap();
+    assert_eq!(water.radical, Some('\u{2f54}'));
+    assert_eq!(water.ideograph, '水');
+    assert_eq!(supplement_forms('\u{2f54}'), vec!['\u{2ea1}', '\u{2ea2}']);
+    assert!(radical_strokes('a').is_empty());
+}
+
+#[test]
+fn readings_of_han() {
+    let han = readings('漢').unwrap();
+    assert_eq!(han.mandarin, "hàn");
+    assert_eq!(han.japanese_on, "KAN");
+    assert_eq!(hangul_readings(han.hangul), vec!["한"]);
+    assert!(readings('a').is_none());
+}
@@ -0,0 +1,209 @@
+//! Validate that files are UTF-8, reporting every invalid byte
+//! sequence along with where it is and what's wrong with it.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::display::ByteRepresentation;
+
+/// What's wrong with an invalid byte sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Continuation bytes (0x80 to 0xBF) that don't follow a lead byte.
+    UnexpectedContinuation,
+    /// A lead byte followed by too few continuation bytes.
+    Incomplete,
+    /// A lead byte whose continuation bytes are missing because the
+    /// file ends.
+    TruncatedAtEof,
+    /// A code point encoded in more bytes than it needs.
+    Overlong(u32),
+    /// A UTF-16 surrogate, which UTF-8 can't encode.
+    Surrogate(u32),
+    /// A code point beyond U+10FFFF.
+    TooLarge(u32),
+    /// A byte that never occurs in UTF-8 (0xF8 to 0xFF).
+    InvalidByte,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match *self {
+            Error::UnexpectedContinuation => write!(f, "unexpected continuation byte"),
+            Error::Incomplete => write!(f, "incomplete sequence"),
+            Error::TruncatedAtEof => write!(f, "sequence truncated at end of file"),
+            Error::Overlong(cp) => write!(f, "overlong encoding of U+{:04X}", cp),
+            Error::Surrogate(cp) => write!(f, "encoded surrogate U+{:04X}", cp),
+            Error::TooLarge(cp) => write!(f, "code point U+{:04X} is beyond U+10FFFF", cp),
+            Error::InvalidByte => write!(f, "byte that never occurs in UTF-8"),
+        }
+    }
+}
+
+/// A single invalid byte sequence found in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invalid {
+    /// 0-based byte offset of the first byte.
+    pub offset: usize,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters, with each invalid
+    /// sequence counting as one.
+    pub column: usize,
+    pub bytes: Vec<u8>,
+    pub error: Error,
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}:{}: byte offset {}: {}: {}",
+            self.line,
+            self.column,
+            self.offset,
+            ByteRepresentation::Utf8(self.bytes.clone()),
+            self.error
+        )
+    }
+}
+
+fn is_continuation(byte: u8) -> bool {
+    (0x80..=0xbf).contains(&byte)
+}
+
+/// Checks the sequence that starts with the lead byte at `bytes[0]`,
+/// returning its length and what's wrong with it, if anything.
+fn check_sequence(bytes: &[u8]) -> (usize, Option<Error>) {
+    let lead = bytes[0];
+    let (len, min) = match lead {
+        0x00..=0x7f => return (1, None),
+        0x80..=0xbf => {
+            let len = bytes.iter().take_while(|&&b| is_continuation(b)).count();
+            return (len, Some(Error::UnexpectedContinuation));
+        }
+        0xc0..=0xdf => (2, 0x80),
+        0xe0..=0xef => (3, 0x800),
+        0xf0..=0xf7 => (4, 0x10000),
+        0xf8..=0xff => return (1, Some(Error::InvalidByte)),
+    };
+    let continuations = bytes[1..]
+        .iter()
+        .take(len - 1)
+        .take_while(|&&b| is_continuation(b))
+        .count();
+    if continuations < len - 1 {
+        let error = if continuations + 1 == bytes.len() {
+            Error::TruncatedAtEof
+        } else {
+            Error::Incomplete
+        };
+        return (continuations + 1, Some(error));
+    }
+    let cp = bytes[1..len]
+        .iter()
+        .fold(u32::from(lead) & (0x7f >> len), |cp, &b| {
+            cp << 6 | u32::from(b & 0x3f)
+        });
+    let error = if cp < min {
+        Some(Error::Overlong(cp))
+    } else if (0xd800..=0xdfff).contains(&cp) {
+        Some(Error::Surrogate(cp))
+    } else if cp > 0x10ffff {
+        Some(Error::TooLarge(cp))
+    } else {
+        None
+    };
+    (len, error)
+}
+
+/// Returns every invalid sequence in `bytes`, in the order they occur.
+pub fn validate_bytes(bytes: &[u8]) -> Vec<Invalid> {
+    let mut invalid = vec![];
+    let (mut offset, mut line, mut column) = (0, 1, 1);
+    while offset < bytes.len() {
+        let (len, error) = check_sequence(&bytes[offset..]);
+        if let Some(error) = error {
+            invalid.push(Invalid {
+                offset,
+                line,
+                column,
+                bytes: bytes[offset..offset + len].to_vec(),
+                error,
+            });
+        }
+        if bytes[offset] == b'\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+        offset += len;
+    }
+    invalid
+}
+
+/// Validates the file at `path`.
+pub fn validate_file(path: &Path) -> io::Result<Vec<Invalid>> {
+    Ok(validate_bytes(&fs::read(path)?))
+}
+
+#[test]
+fn validate_accepts_utf8() {
+    assert!(validate_bytes("Café 😀\n中文\n".as_bytes()).is_empty());
+    assert!(validate_bytes(b"\xef\xbf\xbf\xf4\x8f\xbf\xbf").is_empty());
+}
+
+#[test]
+fn validate_finds_error_classes() {
+    let errors = |bytes: &[u8]| -> Vec<(Vec<u8>, Error)> {
+        validate_bytes(bytes)
+            .into_iter()
+            .map(|invalid| (invalid.bytes, invalid.error))
+            .collect()
+    };
+    assert_eq!(
+        errors(b"a\x80\x80b"),
+        vec![(vec![0x80, 0x80], Error::UnexpectedContinuation)]
+    );
+    assert_eq!(
+        errors(b"\xc0\xaf\xe0\x80\xaf"),
+        vec![
+            (vec![0xc0, 0xaf], Error::Overlong(0x2f)),
+            (vec![0xe0, 0x80, 0xaf], Error::Overlong(0x2f))
+        ]
+    );
+    assert_eq!(
+        errors(b"\xed\xa0\x80"),
+        vec![(vec![0xed, 0xa0, 0x80], Error::Surrogate(0xd800))]
+    );
+    assert_eq!(
+        errors(b"\xf4\x90\x80\x80"),
+        vec![(vec![0xf4, 0x90, 0x80, 0x80], Error::TooLarge(0x110000))]
+    );
+    assert_eq!(
+        errors(b"\xe2\x80x"),
+        vec![(vec![0xe2, 0x80], Error::Incomplete)]
+    );
+    assert_eq!(
+        errors(b"x\xf0\x9f\x98"),
+        vec![(vec![0xf0, 0x9f, 0x98], Error::TruncatedAtEof)]
+    );
+    assert_eq!(errors(b"\xff"), vec![(vec![0xff], Error::InvalidByte)]);
+}
+
+#[test]
+fn validate_reports_positions() {
+    let invalid = validate_bytes(b"ok\nCaf\xe9 \xc3\xa9\xe9\n");
+    let positions: Vec<(usize, usize, usize)> = invalid
+        .iter()
+        .map(|invalid| (invalid.offset, invalid.line, invalid.column))
+        .collect();
+    assert_eq!(positions, vec![(6, 2, 4), (10, 2, 7)]);
+    assert_eq!(
+        invalid[0].to_string(),
+        "2:4: byte offset 6: e9: incomplete sequence"
+    );
+}
@@ -0,0 +1,9 @@
+#!/bin/bash
+
+set -eu -o pipefail
+
+BASE="$(cd "$(dirname "$0")"; pwd)"
+AGL="https://raw.githubusercontent.com/adobe-type-tools/agl-aglfn/master"
+
+curl "${AGL}/glyphlist.txt" -o "${BASE}/glyphlist.txt"
+curl "${AGL}/aglfn.txt" -o "${BASE}/aglfn.txt"
@@ -0,0 +1,15 @@
+#!/bin/bash
+
+set -eu -o pipefail
+
+BASE="$(cd "$(dirname "$0")"; pwd)"
+MAPPINGS="https://www.unicode.org/Public/MAPPINGS"
+
+curl "${MAPPINGS}/VENDORS/MICSFT/PC/CP437.TXT" -o "${BASE}/CP437.TXT"
+curl "${MAPPINGS}/VENDORS/MICSFT/WINDOWS/CP1252.TXT" -o "${BASE}/CP1252.TXT"
+curl "${MAPPINGS}/VENDORS/MICSFT/EBCDIC/CP037.TXT" -o "${BASE}/CP037.TXT"
+curl "${MAPPINGS}/VENDORS/APPLE/ROMAN.TXT" -o "${BASE}/ROMAN.TXT"
+curl "${MAPPINGS}/VENDORS/MISC/KOI8-R.TXT" -o "${BASE}/KOI8-R.TXT"
+for part in 1 2 3 4 5 6 7 8 9 10 11 13 14 15 16; do
+    curl "${MAPPINGS}/ISO8859/8859-${part}.TXT" -o "${BASE}/8859-${part}.TXT"
+done
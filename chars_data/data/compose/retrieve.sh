@@ -0,0 +1,10 @@
+#!/bin/bash
+
+set -eu -o pipefail
+
+BASE="$(cd "$(dirname "$0")"; pwd)"
+
+# The source has XCOMM in place of comments so that it can be run
+# through cpp; the installed table has them turned back into #.
+curl https://gitlab.freedesktop.org/xorg/lib/libx11/-/raw/master/nls/en_US.UTF-8/Compose.pre |
+    sed -e 's/^XCOMM/#/' > "${BASE}/Compose"
@@ -0,0 +1,7 @@
+#!/bin/bash
+
+set -eu -o pipefail
+
+BASE="$(cd "$(dirname "$0")"; pwd)"
+
+curl https://raw.githubusercontent.com/vim/vim/master/runtime/doc/digraph.txt -o "${BASE}/digraph.txt"
@@ -0,0 +1,9 @@
+#!/bin/bash
+
+set -eu -o pipefail
+
+BASE="$(cd "$(dirname "$0")"; pwd)"
+
+for index in jis0208 gb18030 gb18030-ranges big5 euc-kr; do
+    curl "https://encoding.spec.whatwg.org/index-${index}.txt" -o "${BASE}/index-${index}.txt"
+done
@@ -0,0 +1,34 @@
+#!/bin/bash
+
+set -eu -o pipefail
+
+BASE="$(cd "$(dirname "$0")"; pwd)"
+
+# Needs the docutils Python package (`pip install docutils`).
+python3 - "${BASE}/latex.txt" <<'PYTHON'
+import collections
+import sys
+
+from docutils.utils.math import tex2unichar, unichar2tex
+
+commands = collections.defaultdict(list)
+for cp, command in unichar2tex.uni2tex_table.items():
+    command = command.strip()
+    if command.startswith("\\") and len(command) > 1:
+        commands[cp].append(command)
+for table in ["mathaccent", "mathalpha", "mathbin", "mathclose", "mathfence",
+              "mathop", "mathopen", "mathord", "mathover", "mathpunct",
+              "mathradical", "mathrel", "mathunder", "space"]:
+    for name, char in getattr(tex2unichar, table).items():
+        command = "\\" + name
+        if len(char) == 1 and name.strip() and command not in commands[ord(char)]:
+            commands[ord(char)].append(command)
+
+with open(sys.argv[1], "w") as out:
+    out.write("# LaTeX math commands for Unicode characters\n#\n"
+              "# Each line has the code point followed by the commands that produce\n"
+              "# the character, separated by tabs; the first command is the one from\n"
+              "# standard LaTeX or amssymb where there is one.\n")
+    for cp in sorted(commands):
+        out.write("%04X\t%s\n" % (cp, "\t".join(commands[cp])))
+PYTHON
@@ -6,3 +6,15 @@ BASE="$(cd "$(dirname "$0")"; pwd)"
 
 curl http://ftp.unicode.org/Public/UNIDATA/NameAliases.txt -o "${BASE}/NameAliases.txt"
 curl http://ftp.unicode.org/Public/UNIDATA/UnicodeData.txt -o "${BASE}/UnicodeData.txt"
+curl http://ftp.unicode.org/Public/security/latest/confusables.txt -o "${BASE}/confusables.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/SpecialCasing.txt -o "${BASE}/SpecialCasing.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/CaseFolding.txt -o "${BASE}/CaseFolding.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/CompositionExclusions.txt -o "${BASE}/CompositionExclusions.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/EastAsianWidth.txt -o "${BASE}/EastAsianWidth.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/LineBreak.txt -o "${BASE}/LineBreak.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/NamesList.txt -o "${BASE}/NamesList.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/Unihan.zip -o "${BASE}/Unihan.zip"
+unzip -o "${BASE}/Unihan.zip" Unihan_Readings.txt Unihan_IRGSources.txt Unihan_Variants.txt -d "${BASE}"
+rm "${BASE}/Unihan.zip"
+curl http://ftp.unicode.org/Public/UNIDATA/CJKRadicals.txt -o "${BASE}/CJKRadicals.txt"
+curl http://ftp.unicode.org/Public/UNIDATA/DerivedCoreProperties.txt -o "${BASE}/DerivedCoreProperties.txt"
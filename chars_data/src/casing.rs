@@ -0,0 +1,345 @@
+//! Generate case mapping tables that go beyond what the rust standard
+//! library provides: title case, case folding (from CaseFolding.txt),
+//! language- and context-dependent mappings (from SpecialCasing.txt),
+//! and the sets of characters that are equivalent under case folding.
+use std::char;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::unicode;
+
+const SPECIAL_CASING: &[u8] = include_bytes!("../data/unicode/SpecialCasing.txt");
+const CASE_FOLDING: &[u8] = include_bytes!("../data/unicode/CaseFolding.txt");
+
+fn parse_codepoints(hexes: &str) -> Result<String> {
+    hexes
+        .split_whitespace()
+        .map(|hex| {
+            let cp = u32::from_str_radix(hex, 16)
+                .with_context(|| format!("Could not parse {} as base-16 integer", hex))?;
+            char::from_u32(cp).with_context(|| format!("{:?} is not a unicode scalar value", hex))
+        })
+        .collect()
+}
+
+/// Splits a data line into its semicolon-separated fields, dropping
+/// comments. Returns None for lines without data.
+fn data_fields(line: &str) -> Option<Vec<&str>> {
+    let data = line.split('#').next().unwrap_or("");
+    if data.trim().is_empty() {
+        return None;
+    }
+    Some(data.split(';').map(str::trim).collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SpecialCasing {
+    c: char,
+    lower: String,
+    title: String,
+    upper: String,
+    conditions: Vec<String>,
+}
+
+impl fmt::Display for SpecialCasing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "ConditionalCasing{{c:{:?}, lower:{:?}, title:{:?}, upper:{:?}, conditions:&{:?}}},",
+            self.c, self.lower, self.title, self.upper, self.conditions
+        )
+    }
+}
+
+fn process_special_casing_line(line: &str) -> Result<Option<SpecialCasing>> {
+    let fields = match data_fields(line) {
+        None => return Ok(None),
+        Some(fields) => fields,
+    };
+    if fields.len() < 4 {
+        bail!("Not enough fields in special casing line {:?}", line);
+    }
+    let c = parse_codepoints(fields[0])?
+        .chars()
+        .next()
+        .with_context(|| format!("No code point in {:?}", line))?;
+    let conditions = match fields.get(4) {
+        Some(conditions) => conditions.split_whitespace().map(String::from).collect(),
+        None => vec![],
+    };
+    Ok(Some(SpecialCasing {
+        c,
+        lower: parse_codepoints(fields[1])?,
+        title: parse_codepoints(fields[2])?,
+        upper: parse_codepoints(fields[3])?,
+        conditions,
+    }))
+}
+
+#[test]
+fn test_special_casing() {
+    assert_eq!(process_special_casing_line("# comment").unwrap(), None);
+    assert_eq!(
+        process_special_casing_line(
+            "00DF; 00DF; 0053 0073; 0053 0053; # LATIN SMALL LETTER SHARP S"
+        )
+        .unwrap(),
+        Some(SpecialCasing {
+            c: 'ß',
+            lower: "ß".to_string(),
+            title: "Ss".to_string(),
+            upper: "SS".to_string(),
+            conditions: vec![],
+        })
+    );
+    assert_eq!(
+        process_special_casing_line("0307; ; 0307; 0307; tr After_I; # COMBINING DOT ABOVE")
+            .unwrap(),
+        Some(SpecialCasing {
+            c: '\u{307}',
+            lower: "".to_string(),
+            title: "\u{307}".to_string(),
+            upper: "\u{307}".to_string(),
+            conditions: vec!["tr".to_string(), "After_I".to_string()],
+        })
+    );
+}
+
+#[derive(Debug, Default)]
+struct CaseFolding {
+    /// Simple (single character) case folding, status C and S.
+    simple: BTreeMap<char, char>,
+    /// Full case folding, status C and F.
+    full: BTreeMap<char, String>,
+    /// The special Turkic foldings for dotted and dotless I, status T.
+    turkic: BTreeMap<char, char>,
+}
+
+fn process_case_folding_line(folding: &mut CaseFolding, line: &str) -> Result<()> {
+    let fields = match data_fields(line) {
+        None => return Ok(()),
+        Some(fields) => fields,
+    };
+    if fields.len() < 3 {
+        bail!("Not enough fields in case folding line {:?}", line);
+    }
+    let from = parse_codepoints(fields[0])?;
+    let from = from.chars().next().context("Empty case folding source")?;
+    let to = parse_codepoints(fields[2])?;
+    let single = || to.chars().next().context("Empty case folding target");
+    match fields[1] {
+        "C" => {
+            folding.simple.insert(from, single()?);
+            folding.full.insert(from, to.clone());
+        }
+        "S" => {
+            folding.simple.insert(from, single()?);
+        }
+        "F" => {
+            folding.full.insert(from, to.clone());
+        }
+        "T" => {
+            folding.turkic.insert(from, single()?);
+        }
+        status => bail!("Unknown case folding status {:?} in {:?}", status, line),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_case_folding() {
+    let mut folding = CaseFolding::default();
+    for line in &[
+        "# CaseFolding-15.0.0.txt",
+        "004B; C; 006B; # LATIN CAPITAL LETTER K",
+        "1E9E; F; 0073 0073; # LATIN CAPITAL LETTER SHARP S",
+        "1E9E; S; 00DF; # LATIN CAPITAL LETTER SHARP S",
+        "0049; T; 0131; # LATIN CAPITAL LETTER I",
+    ] {
+        process_case_folding_line(&mut folding, line).unwrap();
+    }
+    assert_eq!(folding.simple.get(&'K'), Some(&'k'));
+    assert_eq!(folding.full.get(&'K').map(String::as_str), Some("k"));
+    assert_eq!(folding.simple.get(&'\u{1e9e}'), Some(&'ß'));
+    assert_eq!(
+        folding.full.get(&'\u{1e9e}').map(String::as_str),
+        Some("ss")
+    );
+    assert_eq!(folding.turkic.get(&'I'), Some(&'ı'));
+    assert!(process_case_folding_line(&mut folding, "0041; X; 0061;").is_err());
+}
+
+/// Reads the simple uppercase and titlecase mappings (fields 12 and
+/// 14) from UnicodeData.txt.
+fn read_simple_mappings(
+    reader: impl BufRead,
+) -> Result<(BTreeMap<char, char>, BTreeMap<char, char>)> {
+    let mut upper = BTreeMap::new();
+    let mut title = BTreeMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        let fields: Vec<&str> = line.splitn(15, ';').collect();
+        if fields.len() < 15 {
+            continue;
+        }
+        let c = match u32::from_str_radix(fields[0], 16)
+            .ok()
+            .and_then(char::from_u32)
+        {
+            Some(c) => c,
+            None => continue,
+        };
+        if let Some(u) = parse_codepoints(fields[12])?.chars().next() {
+            upper.insert(c, u);
+        }
+        if let Some(t) = parse_codepoints(fields[14])?.chars().next() {
+            title.insert(c, t);
+        }
+    }
+    Ok((upper, title))
+}
+
+const PREAMBLE: &str = r#"/// Generated with `make names`
+#[derive(Clone, Debug)]
+pub struct ConditionalCasing {
+    pub c: char,
+    pub lower: &'static str,
+    pub title: &'static str,
+    pub upper: &'static str,
+    pub conditions: &'static [&'static str],
+}
+
+"#;
+
+pub fn write_casing_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+
+    let mut unconditional: BTreeMap<char, SpecialCasing> = BTreeMap::new();
+    let mut conditional: Vec<SpecialCasing> = vec![];
+    for line in Cursor::new(SPECIAL_CASING).lines() {
+        if let Some(casing) = process_special_casing_line(line?.as_str())? {
+            if casing.conditions.is_empty() {
+                unconditional.insert(casing.c, casing);
+            } else {
+                conditional.push(casing);
+            }
+        }
+    }
+
+    let mut folding = CaseFolding::default();
+    for line in Cursor::new(CASE_FOLDING).lines() {
+        process_case_folding_line(&mut folding, line?.as_str())?;
+    }
+
+    // Title case is only interesting where it differs from upper case:
+    let (simple_upper, simple_title) = read_simple_mappings(unicode::unicode_data())?;
+    let mut titlecase: BTreeMap<char, String> = BTreeMap::new();
+    let cased: BTreeSet<char> = simple_upper
+        .keys()
+        .chain(simple_title.keys())
+        .chain(unconditional.keys())
+        .cloned()
+        .collect();
+    for c in cased {
+        let (upper, title) = match unconditional.get(&c) {
+            Some(special) => (special.upper.clone(), special.title.clone()),
+            None => {
+                let upper = simple_upper.get(&c).cloned().unwrap_or(c);
+                let title = simple_title.get(&c).cloned().unwrap_or(upper);
+                (upper.to_string(), title.to_string())
+            }
+        };
+        if upper != title {
+            titlecase.insert(c, title);
+        }
+    }
+
+    // Everything that simple-case-folds to the same character is in one
+    // equivalence class:
+    let mut classes: BTreeMap<char, BTreeSet<char>> = BTreeMap::new();
+    for (&from, &to) in folding.simple.iter() {
+        let class = classes.entry(to).or_default();
+        class.insert(from);
+        class.insert(to);
+    }
+
+    let filename = output.join("casing.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    write!(&mut out, "{}", PREAMBLE)?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static TITLECASE: &[(char, &str); {}] = &[",
+        titlecase.len()
+    )?;
+    for (c, title) in titlecase {
+        writeln!(&mut out, "    ({:?}, {:?}),", c, title)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    let folded: BTreeSet<char> = folding
+        .simple
+        .keys()
+        .chain(folding.full.keys())
+        .cloned()
+        .collect();
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CASE_FOLDING: &[(char, char, &str); {}] = &[",
+        folded.len()
+    )?;
+    for c in folded {
+        let simple = folding.simple.get(&c).cloned().unwrap_or(c);
+        let full = folding
+            .full
+            .get(&c)
+            .cloned()
+            .unwrap_or_else(|| simple.to_string());
+        writeln!(&mut out, "    ({:?}, {:?}, {:?}),", c, simple, full)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static TURKIC_CASE_FOLDING: &[(char, char); {}] = &[",
+        folding.turkic.len()
+    )?;
+    for (c, folded) in folding.turkic {
+        writeln!(&mut out, "    ({:?}, {:?}),", c, folded)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CASE_CLASSES: &[(char, &str); {}] = &[",
+        classes.len()
+    )?;
+    for (folded, class) in classes {
+        let class: String = class.into_iter().collect();
+        writeln!(&mut out, "    ({:?}, {:?}),", folded, class)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    conditional.sort_by_key(|casing| casing.c);
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CONDITIONAL_CASING: &[ConditionalCasing; {}] = &[",
+        conditional.len()
+    )?;
+    for casing in conditional {
+        writeln!(&mut out, "    {}", casing)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
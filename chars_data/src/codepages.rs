@@ -0,0 +1,204 @@
+//! Generate byte-to-character tables for legacy single-byte code
+//! pages from the mapping tables in Unicode's MAPPINGS format:
+//!
+//!```text
+//!0x80    0x20AC    #EURO SIGN
+//!0x81              #UNDEFINED
+//!```
+use std::char;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+/// The code pages to generate tables for, other names they go by, and
+/// their mapping tables.
+const CODE_PAGES: &[(&str, &[&str], &[u8])] = &[
+    (
+        "CP437",
+        &["IBM437", "DOS"],
+        include_bytes!("../data/codepages/CP437.TXT"),
+    ),
+    (
+        "CP1252",
+        &["Windows-1252"],
+        include_bytes!("../data/codepages/CP1252.TXT"),
+    ),
+    (
+        "ISO-8859-1",
+        &["Latin1"],
+        include_bytes!("../data/codepages/8859-1.TXT"),
+    ),
+    (
+        "ISO-8859-2",
+        &["Latin2"],
+        include_bytes!("../data/codepages/8859-2.TXT"),
+    ),
+    (
+        "ISO-8859-3",
+        &["Latin3"],
+        include_bytes!("../data/codepages/8859-3.TXT"),
+    ),
+    (
+        "ISO-8859-4",
+        &["Latin4"],
+        include_bytes!("../data/codepages/8859-4.TXT"),
+    ),
+    (
+        "ISO-8859-5",
+        &["Cyrillic"],
+        include_bytes!("../data/codepages/8859-5.TXT"),
+    ),
+    (
+        "ISO-8859-6",
+        &["Arabic"],
+        include_bytes!("../data/codepages/8859-6.TXT"),
+    ),
+    (
+        "ISO-8859-7",
+        &["Greek"],
+        include_bytes!("../data/codepages/8859-7.TXT"),
+    ),
+    (
+        "ISO-8859-8",
+        &["Hebrew"],
+        include_bytes!("../data/codepages/8859-8.TXT"),
+    ),
+    (
+        "ISO-8859-9",
+        &["Latin5"],
+        include_bytes!("../data/codepages/8859-9.TXT"),
+    ),
+    (
+        "ISO-8859-10",
+        &["Latin6"],
+        include_bytes!("../data/codepages/8859-10.TXT"),
+    ),
+    (
+        "ISO-8859-11",
+        &[],
+        include_bytes!("../data/codepages/8859-11.TXT"),
+    ),
+    (
+        "ISO-8859-13",
+        &["Latin7"],
+        include_bytes!("../data/codepages/8859-13.TXT"),
+    ),
+    (
+        "ISO-8859-14",
+        &["Latin8"],
+        include_bytes!("../data/codepages/8859-14.TXT"),
+    ),
+    (
+        "ISO-8859-15",
+        &["Latin9"],
+        include_bytes!("../data/codepages/8859-15.TXT"),
+    ),
+    (
+        "ISO-8859-16",
+        &["Latin10"],
+        include_bytes!("../data/codepages/8859-16.TXT"),
+    ),
+    (
+        "MacRoman",
+        &["Macintosh"],
+        include_bytes!("../data/codepages/ROMAN.TXT"),
+    ),
+    (
+        "KOI8-R",
+        &[],
+        include_bytes!("../data/codepages/KOI8-R.TXT"),
+    ),
+    (
+        "CP037",
+        &["EBCDIC", "IBM037"],
+        include_bytes!("../data/codepages/CP037.TXT"),
+    ),
+];
+
+fn parse_hex(field: &str) -> Result<u32> {
+    let hex = field
+        .strip_prefix("0x")
+        .with_context(|| format!("{:?} doesn't start with 0x", field))?;
+    u32::from_str_radix(hex, 16).with_context(|| format!("Could not parse {} as base-16", hex))
+}
+
+fn process_line(line: &str) -> Result<Option<(u8, Option<char>)>> {
+    let line = line.split('#').next().unwrap_or("");
+    let mut fields = line.split_whitespace();
+    let byte = match fields.next() {
+        Some(byte) => parse_hex(byte)?,
+        None => return Ok(None),
+    };
+    if byte > 0xff {
+        bail!("Byte {:#x} doesn't fit in a single-byte code page", byte);
+    }
+    let c = match fields.next() {
+        Some(cp) => {
+            let cp = parse_hex(cp)?;
+            Some(char::from_u32(cp).with_context(|| format!("{:#x} is not a character", cp))?)
+        }
+        None => None,
+    };
+    Ok(Some((byte as u8, c)))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(process_line("#    Name: cp1252").unwrap(), None);
+    assert_eq!(
+        process_line("0x80\t0x20AC\t#EURO SIGN").unwrap(),
+        Some((0x80, Some('€')))
+    );
+    assert_eq!(
+        process_line("0x81\t      \t#UNDEFINED").unwrap(),
+        Some((0x81, None))
+    );
+    assert!(process_line("0x100\t0x0100").is_err());
+    assert!(process_line("80\t0x20AC").is_err());
+}
+
+fn read_code_page(table: &[u8]) -> Result<[Option<char>; 256]> {
+    let mut chars = [None; 256];
+    for line in Cursor::new(table).lines() {
+        if let Some((byte, c)) = process_line(line?.as_str())? {
+            chars[byte as usize] = c;
+        }
+    }
+    Ok(chars)
+}
+
+const PREAMBLE: &str = r#"/// Generated with `make names`
+#[derive(Debug)]
+pub struct CodePage {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub chars: [Option<char>; 256],
+}
+
+"#;
+
+pub fn write_codepage_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let filename = output.join("codepages.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    write!(&mut out, "{}", PREAMBLE)?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CODE_PAGES: &[CodePage; {}] = &[",
+        CODE_PAGES.len()
+    )?;
+    for (name, aliases, table) in CODE_PAGES {
+        let chars = read_code_page(table).with_context(|| format!("Reading {}", name))?;
+        writeln!(
+            &mut out,
+            "    CodePage{{name:{:?}, aliases:&{:?}, chars:{:?}}},",
+            name, aliases, chars
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Generate the table of X11 Compose key sequences from the
+//! en_US.UTF-8 Compose file:
+//!
+//!```text
+//!<Multi_key> <equal> <slash>        : "≠"    U2260 # NOT EQUAL TO
+//!```
+//!
+//! Keysyms that stand for printable ASCII characters are written as
+//! those characters, and `<Uxxxx>` keysyms as the character they
+//! stand for, unless it is a combining mark; all other keysyms keep
+//! their name in angle brackets.
+use std::char;
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+const COMPOSE: &[u8] = include_bytes!("../data/compose/Compose");
+
+/// The keysym names of printable ASCII characters, other than the
+/// letters and digits, which are named after themselves.
+const ASCII_KEYSYMS: &[(&str, char)] = &[
+    ("exclam", '!'),
+    ("quotedbl", '"'),
+    ("numbersign", '#'),
+    ("dollar", '$'),
+    ("percent", '%'),
+    ("ampersand", '&'),
+    ("apostrophe", '\''),
+    ("parenleft", '('),
+    ("parenright", ')'),
+    ("asterisk", '*'),
+    ("plus", '+'),
+    ("comma", ','),
+    ("minus", '-'),
+    ("period", '.'),
+    ("slash", '/'),
+    ("colon", ':'),
+    ("semicolon", ';'),
+    ("less", '<'),
+    ("equal", '='),
+    ("greater", '>'),
+    ("question", '?'),
+    ("at", '@'),
+    ("bracketleft", '['),
+    ("backslash", '\\'),
+    ("bracketright", ']'),
+    ("asciicircum", '^'),
+    ("underscore", '_'),
+    ("grave", '`'),
+    ("braceleft", '{'),
+    ("bar", '|'),
+    ("braceright", '}'),
+    ("asciitilde", '~'),
+];
+
+/// Writes a keysym the way it is labeled on the keyboard, if it has
+/// a single character label.
+fn keysym_label(keysym: &str) -> String {
+    if keysym.len() == 1 && keysym.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return keysym.to_string();
+    }
+    if let Some(&(_, c)) = ASCII_KEYSYMS.iter().find(|(name, _)| *name == keysym) {
+        return c.to_string();
+    }
+    if let Some(c) = keysym
+        .strip_prefix('U')
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .and_then(char::from_u32)
+    {
+        let combining = unicode_names2::name(c)
+            .into_iter()
+            .any(|name| name.to_string().starts_with("COMBINING"));
+        if !c.is_whitespace() && !c.is_control() && !combining {
+            return c.to_string();
+        }
+    }
+    format!("<{}>", keysym)
+}
+
+/// Parses the quoted result of a Compose line, which may contain
+/// backslash escapes.
+fn parse_result(quoted: &str) -> Option<String> {
+    let mut chars = quoted.trim_start().strip_prefix('"')?.chars();
+    let mut result = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(result),
+            '\\' => result.push(chars.next()?),
+            c => result.push(c),
+        }
+    }
+}
+
+fn process_line(line: &str) -> Result<Option<(char, String)>> {
+    let (keys, result) = match line.split_once(':') {
+        Some(split) if line.starts_with("<Multi_key>") => split,
+        _ => return Ok(None),
+    };
+    let result = match parse_result(result) {
+        Some(result) => result,
+        None => bail!("Expected a quoted result in Compose line {:?}", line),
+    };
+    let mut result_chars = result.chars();
+    let c = match (result_chars.next(), result_chars.next()) {
+        (Some(c), None) => c,
+        _ => return Ok(None),
+    };
+    let labels: Vec<String> = keys
+        .split_whitespace()
+        .skip(1)
+        .map(|key| {
+            key.strip_prefix('<')
+                .and_then(|key| key.strip_suffix('>'))
+                .map(keysym_label)
+                .with_context(|| format!("Malformed keysym {:?} in {:?}", key, line))
+        })
+        .collect::<Result<_>>()?;
+    Ok(Some((c, labels.join(" "))))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(
+        process_line("<Multi_key> <equal> <slash>\t\t: \"≠\"\tU2260 # NOT EQUAL TO").unwrap(),
+        Some(('≠', "= /".to_string()))
+    );
+    assert_eq!(
+        process_line("<Multi_key> <slash> <slash>\t\t: \"\\\\\"\tbackslash # REVERSE SOLIDUS")
+            .unwrap(),
+        Some(('\\', "/ /".to_string()))
+    );
+    assert_eq!(
+        process_line("<Multi_key> <U2395> <space> : \"⍽\" U236D").unwrap(),
+        Some(('⍽', "⎕ <space>".to_string()))
+    );
+    assert_eq!(
+        process_line("<Multi_key> <equal> <U0338> : \"≠\" U2260").unwrap(),
+        Some(('≠', "= <U0338>".to_string()))
+    );
+    assert_eq!(
+        process_line("<Multi_key> <Greek_alpha> <acute> : \"ά\"").unwrap(),
+        Some(('ά', "<Greek_alpha> <acute>".to_string()))
+    );
+    assert_eq!(
+        process_line("<dead_tilde> <space> : \"~\" asciitilde").unwrap(),
+        None
+    );
+    assert_eq!(
+        process_line("<Multi_key> <quotedbl> <backslash> : \"〃\"").unwrap(),
+        Some(('〃', "\" \\".to_string()))
+    );
+    assert!(process_line("<Multi_key> <a> : nothing").is_err());
+}
+
+pub fn write_compose_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut sequences: BTreeMap<char, Vec<String>> = BTreeMap::new();
+    for line in Cursor::new(COMPOSE).lines() {
+        if let Some((c, sequence)) = process_line(line?.as_str())? {
+            let entry = sequences.entry(c).or_default();
+            if !entry.contains(&sequence) {
+                entry.push(sequence);
+            }
+        }
+    }
+
+    let filename = output.join("compose.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static COMPOSE_SEQUENCES: &[(char, &[&str]); {}] = &[",
+        sequences.len()
+    )?;
+    for (c, mut sequences) in sequences {
+        // Shorter sequences are easier to remember:
+        sequences.sort_by_key(|s| s.split(' ').count());
+        writeln!(&mut out, "    ({:?}, &{:?}),", c, sequences)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! Generate the table of visually confusable characters from UTS #39's
+//! confusables.txt.
+//!
+//! Each data line maps a source character to its "prototype", the
+//! sequence of characters it is most easily mistaken for:
+//!
+//!```text
+//!0430 ; 0061 ; MA #* ( а → a ) CYRILLIC SMALL LETTER A → LATIN SMALL LETTER A #
+//!```
+use std::char;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+const CONFUSABLES: &[u8] = include_bytes!("../data/unicode/confusables.txt");
+
+fn parse_codepoint(hex: &str) -> Result<char> {
+    let cp = u32::from_str_radix(hex, 16)
+        .with_context(|| format!("Could not parse {} as base-16 integer", hex))?;
+    char::from_u32(cp).with_context(|| format!("{:?} is not a unicode scalar value", hex))
+}
+
+fn process_line(line: &str) -> Result<Option<(char, String)>> {
+    let line = line.trim_start_matches('\u{feff}');
+    let data = line.split('#').next().unwrap_or("");
+    if data.trim().is_empty() {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = data.split(';').map(str::trim).collect();
+    if fields.len() < 2 {
+        bail!("Not enough fields in confusables line {:?}", line);
+    }
+    let source = parse_codepoint(fields[0])?;
+    let prototype = fields[1]
+        .split_whitespace()
+        .map(parse_codepoint)
+        .collect::<Result<String>>()?;
+    Ok(Some((source, prototype)))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(process_line("# confusables.txt").unwrap(), None);
+    assert_eq!(process_line("\u{feff}# confusables.txt").unwrap(), None);
+    assert_eq!(process_line("").unwrap(), None);
+    assert_eq!(
+        process_line(
+            "0430 ;\t0061 ;\tMA\t#* ( а → a ) CYRILLIC SMALL LETTER A → LATIN SMALL LETTER A\t# "
+        )
+        .unwrap(),
+        Some(('\u{430}', "a".to_string()))
+    );
+    assert_eq!(
+        process_line("33A1 ;\t006D 00B2 ;\tMA\t#* ( ㎡ → m² ) SQUARE M SQUARED → LATIN SMALL LETTER M, SUPERSCRIPT TWO\t# ").unwrap(),
+        Some(('\u{33a1}', "m\u{b2}".to_string()))
+    );
+}
+
+pub fn write_confusables_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut prototypes: BTreeMap<char, String> = BTreeMap::new();
+    let mut classes: BTreeMap<String, BTreeSet<char>> = BTreeMap::new();
+    for line in Cursor::new(CONFUSABLES).lines() {
+        if let Some((source, prototype)) = process_line(line?.as_str())? {
+            classes.entry(prototype.clone()).or_default().insert(source);
+            prototypes.insert(source, prototype);
+        }
+    }
+
+    let filename = output.join("confusables.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static PROTOTYPES: &[(char, &str); {}] = &[",
+        prototypes.len()
+    )?;
+    for (source, prototype) in prototypes {
+        writeln!(&mut out, "    ({:?}, {:?}),", source, prototype)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CONFUSABLE_CLASSES: &[(&str, &str); {}] = &[",
+        classes.len()
+    )?;
+    for (prototype, sources) in classes {
+        let sources: String = sources.into_iter().collect();
+        writeln!(&mut out, "    ({:?}, {:?}),", prototype, sources)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
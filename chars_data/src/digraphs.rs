@@ -0,0 +1,82 @@
+//! Generate the table of Vim's digraphs (mostly the two-character
+//! mnemonics of RFC 1345, plus Vim's own additions) from the tables
+//! in Vim's documentation:
+//!
+//!```text
+//!char  digraph    hex    dec    official name ~
+//!ä    a:    0xe4    228    LATIN SMALL LETTER A WITH DIAERESIS
+//!→    ->    2192    8594    RIGHTWARDS ARROW
+//!```
+//!
+//! Only lines with all five tab-separated columns, whose hexadecimal
+//! and decimal code points agree, are table rows; the rest of the file
+//! is prose.
+use std::char;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+const DIGRAPHS: &[u8] = include_bytes!("../data/digraphs/digraph.txt");
+
+fn process_line(line: &str) -> Option<(char, String)> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 5 || fields[1].chars().count() != 2 {
+        return None;
+    }
+    let hex = fields[2].trim();
+    let cp = u32::from_str_radix(hex.strip_prefix("0x").unwrap_or(hex), 16).ok()?;
+    if fields[3].trim().parse::<u32>().ok()? != cp {
+        return None;
+    }
+    Some((char::from_u32(cp)?, fields[1].to_string()))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(
+        process_line("ä\ta:\t0xe4\t228\tLATIN SMALL LETTER A WITH DIAERESIS"),
+        Some(('ä', "a:".to_string()))
+    );
+    assert_eq!(
+        process_line("→\t->\t2192\t8594\tRIGHTWARDS ARROW"),
+        Some(('→', "->".to_string()))
+    );
+    assert_eq!(
+        process_line("^@\tNU\t0x00\t  0\tNULL (NUL)"),
+        Some(('\0', "NU".to_string()))
+    );
+    assert_eq!(
+        process_line("char  digraph\thex\tdec\tofficial name ~"),
+        None
+    );
+    assert_eq!(process_line("ä\ta:\t0xe4\t229\tMISMATCH"), None);
+}
+
+pub fn write_digraph_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut digraphs: Vec<(char, String)> = vec![];
+    for line in Cursor::new(DIGRAPHS).lines() {
+        digraphs.extend(process_line(line?.as_str()));
+    }
+    // Keep the order of the table for characters with several digraphs:
+    digraphs.sort_by_key(|&(c, _)| c);
+    digraphs.dedup();
+
+    let filename = output.join("digraphs.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static DIGRAPHS: &[(char, &str); {}] = &[",
+        digraphs.len()
+    )?;
+    for (c, digraph) in digraphs {
+        writeln!(&mut out, "    ({:?}, {:?}),", c, digraph)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Generate the tables for multi-byte East Asian encodings from the
+//! indexes of the WHATWG Encoding Standard:
+//!
+//!```text
+//!    0    0x3000    　 (IDEOGRAPHIC SPACE)
+//!```
+//!
+//! Each line maps a pointer, which the encoding computes from the
+//! bytes, to a code point. The tables are sorted by character, so that
+//! encoding is a binary search; decoding scans for the pointer.
+use std::char;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+/// The indexes to generate tables for: the name of the table and the
+/// index file.
+const INDEXES: &[(&str, &[u8])] = &[
+    (
+        "JIS0208",
+        include_bytes!("../data/encodings/index-jis0208.txt"),
+    ),
+    (
+        "GB18030",
+        include_bytes!("../data/encodings/index-gb18030.txt"),
+    ),
+    ("BIG5", include_bytes!("../data/encodings/index-big5.txt")),
+    (
+        "EUC_KR",
+        include_bytes!("../data/encodings/index-euc-kr.txt"),
+    ),
+];
+
+const GB18030_RANGES: &[u8] = include_bytes!("../data/encodings/index-gb18030-ranges.txt");
+
+fn process_line(line: &str) -> Result<Option<(u32, char)>> {
+    if line.starts_with('#') || line.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut fields = line.split('\t');
+    let pointer = fields.next().unwrap_or_default().trim();
+    let pointer = pointer
+        .parse::<u32>()
+        .with_context(|| format!("Could not parse pointer {:?}", pointer))?;
+    let cp = match fields.next().and_then(|cp| cp.strip_prefix("0x")) {
+        Some(hex) => u32::from_str_radix(hex, 16)
+            .with_context(|| format!("Could not parse {:?} as base-16 integer", hex))?,
+        None => bail!("Expected a code point in index line {:?}", line),
+    };
+    let c = char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))?;
+    Ok(Some((pointer, c)))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(process_line("# Index JIS0208").unwrap(), None);
+    assert_eq!(
+        process_line("    0\t0x3000\t\u{3000} (IDEOGRAPHIC SPACE)").unwrap(),
+        Some((0, '\u{3000}'))
+    );
+    assert_eq!(
+        process_line("    36\t0x00A5").unwrap(),
+        Some((36, '\u{a5}'))
+    );
+    assert!(process_line("0x3000\t0").is_err());
+    assert!(process_line("    0").is_err());
+}
+
+fn read_index(index: &[u8]) -> Result<Vec<(u32, char)>> {
+    let mut entries = vec![];
+    for line in Cursor::new(index).lines() {
+        entries.extend(process_line(line?.as_str())?);
+    }
+    Ok(entries)
+}
+
+pub fn write_encoding_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let filename = output.join("encodings.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    for (name, index) in INDEXES {
+        let mut entries = read_index(index).with_context(|| format!("Reading {}", name))?;
+        // Where a character has several pointers, encoding uses the
+        // first one:
+        entries.sort_by_key(|&(pointer, c)| (c, pointer));
+        writeln!(&mut out, "#[rustfmt::skip]")?;
+        writeln!(
+            &mut out,
+            "pub static {}: &[(char, u16); {}] = &[",
+            name,
+            entries.len()
+        )?;
+        for (pointer, c) in entries {
+            if pointer > u32::from(u16::MAX) {
+                bail!("Pointer {} in {} is too large", pointer, name);
+            }
+            writeln!(&mut out, "    ({:?}, {}),", c, pointer)?;
+        }
+        writeln!(&mut out, "];")?;
+    }
+
+    let ranges = read_index(GB18030_RANGES).context("Reading GB18030 ranges")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static GB18030_RANGES: &[(u32, char); {}] = &[",
+        ranges.len()
+    )?;
+    for (pointer, c) in ranges {
+        writeln!(&mut out, "    ({}, {:?}),", pointer, c)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
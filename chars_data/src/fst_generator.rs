@@ -75,6 +75,36 @@ fn test_name_components() {
     );
 }
 
+/// Words that phrases like informative aliases are written with, but
+/// that are no use to search for.
+fn build_phrase_stopwords() -> BTreeSet<&'static str> {
+    vec![
+        "an", "and", "as", "at", "by", "in", "is", "of", "on", "or", "the", "to",
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn phrase_components(phrase: &str) -> Vec<String> {
+    lazy_static! {
+        static ref PHRASE_STOPS: BTreeSet<&'static str> = build_phrase_stopwords();
+    }
+    let mut result = name_components(phrase);
+    result.retain(|component| !PHRASE_STOPS.contains(component.as_str()));
+    result
+}
+
+#[test]
+fn test_phrase_components() {
+    assert_eq!(
+        phrase_components("hyphen or minus sign"),
+        vec!["hyphen or minus sign", "hyphen", "minus"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<String>>()
+    );
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Names {
     map: BTreeMap<String, BTreeSet<char>>,
@@ -96,6 +126,25 @@ impl Names {
         }
     }
 
+    /// Insert phrases like informative aliases, whose words are
+    /// indexed minus stopwords and the words they are phrased with.
+    pub fn insert_phrases(&mut self, phrases: Vec<String>, ch: char) {
+        for phrase in phrases {
+            for component in phrase_components(phrase.as_str()) {
+                self.map.entry(component).or_default().insert(ch);
+            }
+        }
+    }
+
+    /// Insert search terms as they are, without splitting them into
+    /// components, so that prefixed terms like `definition:milky way`
+    /// don't leak their words into the name index.
+    pub fn insert_terms(&mut self, terms: Vec<String>, ch: char) {
+        for term in terms {
+            self.map.entry(term.to_lowercase()).or_default().insert(ch);
+        }
+    }
+
     pub fn iter(&self) -> btree_map::Iter<'_, String, BTreeSet<char>> {
         self.map.iter()
     }
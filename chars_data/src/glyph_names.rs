@@ -0,0 +1,120 @@
+//! Generate the tables of PostScript glyph names from the Adobe
+//! Glyph List (glyph name to code points):
+//!
+//!```text
+//!Euro;20AC
+//!dalethatafpatah;05D3 05B2
+//!```
+//!
+//! and the Adobe Glyph List For New Fonts (the recommended name for
+//! a code point):
+//!
+//!```text
+//!20AC;Euro;EURO SIGN
+//!```
+use std::char;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+const GLYPH_LIST: &[u8] = include_bytes!("../data/agl/glyphlist.txt");
+const AGLFN: &[u8] = include_bytes!("../data/agl/aglfn.txt");
+
+fn parse_char(hex: &str) -> Result<char> {
+    let cp = u32::from_str_radix(hex, 16)
+        .with_context(|| format!("Could not parse {:?} as base-16 integer", hex))?;
+    char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))
+}
+
+fn process_glyph_list_line(line: &str) -> Result<Option<(String, String)>> {
+    if line.starts_with('#') || line.trim().is_empty() {
+        return Ok(None);
+    }
+    let (name, code_points) = match line.split_once(';') {
+        Some(fields) => fields,
+        None => bail!("Expected two fields in glyph list line {:?}", line),
+    };
+    let chars = code_points
+        .split_whitespace()
+        .map(parse_char)
+        .collect::<Result<String>>()?;
+    Ok(Some((name.to_string(), chars)))
+}
+
+#[test]
+fn test_process_glyph_list_line() {
+    assert_eq!(process_glyph_list_line("# comment").unwrap(), None);
+    assert_eq!(
+        process_glyph_list_line("Euro;20AC").unwrap(),
+        Some(("Euro".to_string(), "€".to_string()))
+    );
+    assert_eq!(
+        process_glyph_list_line("dalethatafpatah;05D3 05B2").unwrap(),
+        Some(("dalethatafpatah".to_string(), "\u{5d3}\u{5b2}".to_string()))
+    );
+    assert!(process_glyph_list_line("Euro").is_err());
+}
+
+fn process_aglfn_line(line: &str) -> Result<Option<(char, String)>> {
+    if line.starts_with('#') || line.trim().is_empty() {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = line.split(';').collect();
+    if fields.len() < 2 {
+        bail!("Expected at least two fields in AGLFN line {:?}", line);
+    }
+    Ok(Some((parse_char(fields[0])?, fields[1].to_string())))
+}
+
+#[test]
+fn test_process_aglfn_line() {
+    assert_eq!(
+        process_aglfn_line("20AC;Euro;EURO SIGN").unwrap(),
+        Some(('€', "Euro".to_string()))
+    );
+    assert_eq!(process_aglfn_line("# comment").unwrap(), None);
+    assert!(process_aglfn_line("20AC").is_err());
+}
+
+pub fn write_glyph_name_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut glyph_list: Vec<(String, String)> = vec![];
+    for line in Cursor::new(GLYPH_LIST).lines() {
+        glyph_list.extend(process_glyph_list_line(line?.as_str())?);
+    }
+    glyph_list.sort();
+    let mut aglfn: Vec<(char, String)> = vec![];
+    for line in Cursor::new(AGLFN).lines() {
+        aglfn.extend(process_aglfn_line(line?.as_str())?);
+    }
+    aglfn.sort();
+
+    let filename = output.join("glyph_names.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static GLYPH_LIST: &[(&str, &str); {}] = &[",
+        glyph_list.len()
+    )?;
+    for (name, chars) in glyph_list {
+        writeln!(&mut out, "    ({:?}, {:?}),", name, chars)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static AGLFN: &[(char, &str); {}] = &[",
+        aglfn.len()
+    )?;
+    for (c, name) in aglfn {
+        writeln!(&mut out, "    ({:?}, {:?}),", c, name)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! Generate the table of X11 keysyms for characters from
+//! keysymdef.h:
+//!
+//!```text
+//!#define XK_Greek_lambda        0x07eb  /* U+03BB GREEK SMALL LETTER LAMDA */
+//!```
+//!
+//! Keysyms whose character is given in parentheses only approximate
+//! it, and are left out.
+use std::char;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+const KEYSYMDEF: &[u8] = include_bytes!("../data/keysyms/keysymdef.h");
+
+fn process_line(line: &str) -> Result<Option<(char, String, u32)>> {
+    lazy_static! {
+        static ref DEFINE: Regex =
+            Regex::new(r"^#define XK_([a-zA-Z0-9_]+)\s+0x([0-9a-fA-F]+)\s*/\* U\+([0-9A-Fa-f]+) ")
+                .unwrap();
+    }
+    let captures = match DEFINE.captures(line) {
+        Some(captures) => captures,
+        None => return Ok(None),
+    };
+    let value = u32::from_str_radix(&captures[2], 16)
+        .with_context(|| format!("Could not parse keysym value in {:?}", line))?;
+    let cp = u32::from_str_radix(&captures[3], 16)
+        .with_context(|| format!("Could not parse code point in {:?}", line))?;
+    let c = char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))?;
+    Ok(Some((c, captures[1].to_string(), value)))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(
+        process_line(
+            "#define XK_Greek_lambda                  0x07eb  /* U+03BB GREEK SMALL LETTER LAMDA */"
+        )
+        .unwrap(),
+        Some(('λ', "Greek_lambda".to_string(), 0x7eb))
+    );
+    assert_eq!(
+        process_line("#define XK_quoteright                    0x0027  /* deprecated */").unwrap(),
+        None
+    );
+    assert_eq!(
+        process_line("#define XK_overline 0x047e  /*(U+203E OVERLINE)*/").unwrap(),
+        None
+    );
+    assert_eq!(
+        process_line("#define XK_BackSpace                     0xff08  /* U+0008 BACKSPACE */")
+            .unwrap(),
+        Some(('\u{8}', "BackSpace".to_string(), 0xff08))
+    );
+}
+
+pub fn write_keysym_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut keysyms: Vec<(char, String, u32)> = vec![];
+    for line in Cursor::new(KEYSYMDEF).lines() {
+        keysyms.extend(process_line(line?.as_str())?);
+    }
+    // Keep the order of the file for characters with several keysyms:
+    keysyms.sort_by_key(|&(c, _, _)| c);
+
+    let filename = output.join("keysyms.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static KEYSYMS: &[(char, &str, u32); {}] = &[",
+        keysyms.len()
+    )?;
+    for (c, name, value) in keysyms {
+        writeln!(&mut out, "    ({:?}, {:?}, {:#06x}),", c, name, value)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
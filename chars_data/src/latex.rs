@@ -0,0 +1,177 @@
+//! Generate the table of LaTeX commands for characters, from the
+//! table of math commands:
+//!
+//!```text
+//!2192    \rightarrow    \to
+//!211D    \mathbb{R}
+//!```
+//!
+//! and from the names of accented Latin letters, which get the
+//! text-mode accent commands (like `\"{o}` for ö).
+use std::char;
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+const LATEX: &[u8] = include_bytes!("../data/latex/latex.txt");
+
+/// The text-mode accent commands, by the accent's name in Unicode
+/// character names.
+const ACCENTS: &[(&str, &str)] = &[
+    ("GRAVE", "`"),
+    ("ACUTE", "'"),
+    ("CIRCUMFLEX", "^"),
+    ("DIAERESIS", "\""),
+    ("TILDE", "~"),
+    ("MACRON", "="),
+    ("DOT ABOVE", "."),
+    ("BREVE", "u"),
+    ("CARON", "v"),
+    ("DOUBLE ACUTE", "H"),
+    ("CEDILLA", "c"),
+    ("OGONEK", "k"),
+    ("RING ABOVE", "r"),
+    ("DOT BELOW", "d"),
+    ("LINE BELOW", "b"),
+];
+
+/// Text-mode commands for letters and symbols that aren't math.
+const TEXT_COMMANDS: &[(char, &str)] = &[
+    ('ß', "\\ss"),
+    ('æ', "\\ae"),
+    ('Æ', "\\AE"),
+    ('œ', "\\oe"),
+    ('Œ', "\\OE"),
+    ('ø', "\\o"),
+    ('Ø', "\\O"),
+    ('å', "\\aa"),
+    ('Å', "\\AA"),
+    ('ł', "\\l"),
+    ('Ł', "\\L"),
+    ('ı', "\\i"),
+    ('ȷ', "\\j"),
+    ('¡', "\\textexclamdown"),
+    ('¿', "\\textquestiondown"),
+    ('©', "\\copyright"),
+    ('®', "\\textregistered"),
+    ('™', "\\texttrademark"),
+    ('°', "\\textdegree"),
+    ('€', "\\texteuro"),
+    ('–', "\\textendash"),
+    ('—', "\\textemdash"),
+    ('‘', "\\textquoteleft"),
+    ('’', "\\textquoteright"),
+    ('“', "\\textquotedblleft"),
+    ('”', "\\textquotedblright"),
+    ('•', "\\textbullet"),
+    ('…', "\\textellipsis"),
+    ('†', "\\dag"),
+    ('‡', "\\ddag"),
+];
+
+fn process_line(line: &str) -> Result<Option<(char, Vec<String>)>> {
+    if line.starts_with('#') || line.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut fields = line.split('\t');
+    let hex = fields.next().unwrap_or_default();
+    let cp = u32::from_str_radix(hex, 16)
+        .with_context(|| format!("Could not parse {:?} as base-16 integer", hex))?;
+    let c = char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))?;
+    Ok(Some((c, fields.map(str::to_string).collect())))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(process_line("# comment").unwrap(), None);
+    assert_eq!(
+        process_line("2192\t\\rightarrow\t\\to").unwrap(),
+        Some(('→', vec!["\\rightarrow".to_string(), "\\to".to_string()]))
+    );
+    assert!(process_line("\\alpha\t03B1").is_err());
+}
+
+/// Returns the text-mode accent command for an accented Latin letter
+/// with the given name.
+fn accent_command(name: &str) -> Option<String> {
+    lazy_static! {
+        static ref ACCENTED: Regex =
+            Regex::new(r"^LATIN (CAPITAL|SMALL) LETTER ([A-Z]) WITH (.+)$").unwrap();
+    }
+    let captures = ACCENTED.captures(name)?;
+    let accent = ACCENTS
+        .iter()
+        .find(|&&(name, _)| name == &captures[3])
+        .map(|&(_, accent)| accent)?;
+    let letter = match &captures[1] {
+        "SMALL" => captures[2].to_lowercase(),
+        _ => captures[2].to_string(),
+    };
+    Some(format!("\\{}{{{}}}", accent, letter))
+}
+
+#[test]
+fn test_accent_command() {
+    assert_eq!(
+        accent_command("LATIN SMALL LETTER O WITH DIAERESIS"),
+        Some("\\\"{o}".to_string())
+    );
+    assert_eq!(
+        accent_command("LATIN CAPITAL LETTER C WITH CEDILLA"),
+        Some("\\c{C}".to_string())
+    );
+    assert_eq!(
+        accent_command("LATIN SMALL LETTER O WITH DIAERESIS AND MACRON"),
+        None
+    );
+    assert_eq!(accent_command("LATIN SMALL LETTER SHARP S"), None);
+}
+
+pub fn write_latex_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut commands: BTreeMap<char, Vec<String>> = BTreeMap::new();
+    for line in Cursor::new(LATEX).lines() {
+        if let Some((c, math)) = process_line(line?.as_str())? {
+            commands.entry(c).or_default().extend(math);
+        }
+    }
+    for &(c, command) in TEXT_COMMANDS {
+        commands.entry(c).or_default().push(command.to_string());
+    }
+    let latin = ('\u{c0}'..='\u{24f}').chain('\u{1e00}'..='\u{1eff}');
+    for c in latin {
+        let name = match unicode_names2::name(c) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        if let Some(command) = accent_command(&name) {
+            commands.entry(c).or_default().push(command);
+        }
+    }
+
+    let filename = output.join("latex.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static LATEX_COMMANDS: &[(char, &[&str]); {}] = &[",
+        commands.len()
+    )?;
+    for (c, commands) in commands {
+        let mut unique: Vec<String> = vec![];
+        for command in commands {
+            if !unique.contains(&command) {
+                unique.push(command);
+            }
+        }
+        writeln!(&mut out, "    ({:?}, &{:?}),", c, unique)?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
@@ -7,8 +7,21 @@ use anyhow::{Context, Result};
 use std::path::Path;
 
 mod ascii;
+mod casing;
+mod codepages;
+mod compose;
+mod confusables;
+mod digraphs;
+mod encodings;
 mod fst_generator;
+mod glyph_names;
+mod keysyms;
+mod latex;
+mod names_list;
+mod normalization;
+mod properties;
 mod unicode;
+mod unihan;
 
 /// Runs the code generator and writes files.
 pub fn generate_files(src_dir: &Path) -> Result<()> {
@@ -21,7 +34,32 @@ pub fn generate_files(src_dir: &Path) -> Result<()> {
         .context("Reading unicode name aliases")?;
     unicode::read_names(&mut sorted_names, unicode::unicode_data())
         .context("Reading unicode data")?;
+    names_list::read_informative_aliases(&mut sorted_names)
+        .context("Reading informative aliases")?;
+    unihan::read_unihan_names(&mut sorted_names).context("Reading Unihan names")?;
     unicode::write_name_data(&sorted_names, &src_dir.join("unicode/"))
         .context("Writing unicode name data")?;
+    unicode::write_alias_data(unicode::name_aliases(), &src_dir.join("unicode/"))
+        .context("Writing unicode name alias data")?;
+    unicode::write_old_name_data(unicode::unicode_data(), &src_dir.join("unicode/"))
+        .context("Writing unicode 1.0 name data")?;
+    names_list::write_names_list_data(&src_dir.join("unicode/"))
+        .context("Writing names list data")?;
+    normalization::write_normalization_data(&src_dir.join("unicode/"))
+        .context("Writing normalization data")?;
+    confusables::write_confusables_data(&src_dir.join("unicode/"))
+        .context("Writing confusables data")?;
+    casing::write_casing_data(&src_dir.join("unicode/")).context("Writing casing data")?;
+    properties::write_property_data(&src_dir.join("unicode/")).context("Writing property data")?;
+    unihan::write_unihan_data(&src_dir.join("unicode/")).context("Writing Unihan data")?;
+    compose::write_compose_data(&src_dir.join("input/")).context("Writing Compose data")?;
+    digraphs::write_digraph_data(&src_dir.join("input/")).context("Writing digraph data")?;
+    keysyms::write_keysym_data(&src_dir.join("notations/")).context("Writing keysym data")?;
+    glyph_names::write_glyph_name_data(&src_dir.join("notations/"))
+        .context("Writing glyph name data")?;
+    latex::write_latex_data(&src_dir.join("notations/")).context("Writing LaTeX data")?;
+    codepages::write_codepage_data(&src_dir.join("codepages/"))
+        .context("Writing code page data")?;
+    encodings::write_encoding_data(&src_dir.join("codepages/")).context("Writing encoding data")?;
     Ok(())
 }
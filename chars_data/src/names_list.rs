@@ -0,0 +1,256 @@
+//! Generate tables for the editorial annotations in NamesList.txt,
+//! the file that the Unicode code charts are typeset from:
+//!
+//!```text
+//!@        ASCII punctuation and symbols
+//!002D    HYPHEN-MINUS
+//!    = hyphen or minus sign
+//!    * used for either hyphen or minus sign
+//!    x (hyphen - 2010)
+//!    x (minus sign - 2212)
+//!```
+//!
+//! Lines starting with `=` are informative aliases, `*` are notes,
+//! `x` are cross references to other characters, and `@` lines are the
+//! subheaders that group characters in the charts.
+use std::char;
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::fst_generator;
+
+const NAMES_LIST: &[u8] = include_bytes!("../data/unicode/NamesList.txt");
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Annotations {
+    aliases: Vec<String>,
+    notes: Vec<String>,
+    see_also: Vec<char>,
+}
+
+/// A run of characters under the same chart subheader.
+#[derive(Debug, Clone, PartialEq)]
+struct Subheader {
+    start: char,
+    end: char,
+    name: String,
+}
+
+#[derive(Debug, Default)]
+struct NamesList {
+    annotations: BTreeMap<char, Annotations>,
+    subheaders: Vec<Subheader>,
+}
+
+#[derive(Debug, Default)]
+struct Parser {
+    list: NamesList,
+    current: Option<char>,
+    subheader: Option<String>,
+    /// Whether no character has been seen under the current subheader
+    /// yet.
+    fresh: bool,
+}
+
+fn parse_hex(hex: &str) -> Option<char> {
+    u32::from_str_radix(hex.trim(), 16)
+        .ok()
+        .and_then(char::from_u32)
+}
+
+/// Parses the target of a cross reference, which is either a bare
+/// code point (`x 2212`) or a name with the code point
+/// (`x (minus sign - 2212)`).
+fn parse_cross_reference(reference: &str) -> Option<char> {
+    let reference = reference.trim();
+    match reference
+        .strip_prefix('(')
+        .and_then(|r| r.strip_suffix(')'))
+    {
+        Some(inner) => parse_hex(inner.rsplit(" - ").next()?),
+        None => parse_hex(reference.split_whitespace().next()?),
+    }
+}
+
+#[test]
+fn test_parse_cross_reference() {
+    assert_eq!(parse_cross_reference(" (hyphen - 2010)"), Some('\u{2010}'));
+    assert_eq!(parse_cross_reference(" 2212"), Some('\u{2212}'));
+    assert_eq!(parse_cross_reference(" (<control> - 0085)"), Some('\u{85}'));
+    assert_eq!(parse_cross_reference(" (nonsense)"), None);
+}
+
+impl Parser {
+    fn process_line(&mut self, line: &str) {
+        if let Some(annotation) = line.strip_prefix('\t') {
+            let c = match self.current {
+                Some(c) => c,
+                None => return,
+            };
+            // Decompositions (`:` and `#`), formal aliases (`%`) and
+            // variation sequences (`~`) are available elsewhere:
+            let annotations = &mut self.list.annotations;
+            if let Some(alias) = annotation.strip_prefix("= ") {
+                let entry = annotations.entry(c).or_default();
+                entry.aliases.push(alias.trim().to_string());
+            } else if let Some(note) = annotation.strip_prefix("* ") {
+                let entry = annotations.entry(c).or_default();
+                entry.notes.push(note.trim().to_string());
+            } else if let Some(reference) = annotation.strip_prefix("x ") {
+                if let Some(other) = parse_cross_reference(reference) {
+                    annotations.entry(c).or_default().see_also.push(other);
+                }
+            }
+        } else if line.starts_with("@@") {
+            // A block header: subheaders don't carry over into the next
+            // block.
+            self.current = None;
+            self.subheader = None;
+        } else if let Some(subheader) = line.strip_prefix("@\t") {
+            self.current = None;
+            self.subheader = Some(subheader.trim().to_string());
+            self.fresh = true;
+        } else if line.starts_with('@') || line.starts_with(';') {
+            self.current = None;
+        } else {
+            let mut fields = line.splitn(2, '\t');
+            self.current = fields.next().and_then(parse_hex);
+            if let (Some(c), Some(name)) = (self.current, self.subheader.as_ref()) {
+                match self.list.subheaders.last_mut() {
+                    Some(last) if !self.fresh => last.end = c,
+                    _ => self.list.subheaders.push(Subheader {
+                        start: c,
+                        end: c,
+                        name: name.clone(),
+                    }),
+                }
+                self.fresh = false;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_process_line() {
+    let mut parser = Parser::default();
+    for line in &[
+        "@@\t0000\tC0 Controls and Basic Latin\t007F",
+        "@\t\tASCII punctuation and symbols",
+        "002C\tCOMMA",
+        "\t* used as decimal separator in many countries",
+        "002D\tHYPHEN-MINUS",
+        "\t= hyphen or minus sign",
+        "\t* used for either hyphen or minus sign",
+        "\tx (hyphen - 2010)",
+        "\tx 2212",
+        "@\t\tASCII digits",
+        "0030\tDIGIT ZERO",
+        "\t# 0030 0020",
+        "@@\t0080\tC1 Controls and Latin-1 Supplement\t00FF",
+        "00A0\tNO-BREAK SPACE",
+        "\t= NBSP",
+    ] {
+        parser.process_line(line);
+    }
+    let list = parser.list;
+    assert_eq!(
+        list.annotations.get(&'-'),
+        Some(&Annotations {
+            aliases: vec!["hyphen or minus sign".to_string()],
+            notes: vec!["used for either hyphen or minus sign".to_string()],
+            see_also: vec!['\u{2010}', '\u{2212}'],
+        })
+    );
+    assert_eq!(list.annotations.get(&'0'), None);
+    assert_eq!(
+        list.subheaders,
+        vec![
+            Subheader {
+                start: ',',
+                end: '-',
+                name: "ASCII punctuation and symbols".to_string()
+            },
+            Subheader {
+                start: '0',
+                end: '0',
+                name: "ASCII digits".to_string()
+            },
+        ]
+    );
+}
+
+fn read_names_list(reader: impl BufRead) -> Result<NamesList> {
+    let mut parser = Parser::default();
+    for line in reader.lines() {
+        parser.process_line(line?.as_str());
+    }
+    Ok(parser.list)
+}
+
+/// Adds the informative aliases from NamesList.txt to the names that
+/// characters can be found by.
+pub fn read_informative_aliases(names: &mut fst_generator::Names) -> Result<()> {
+    let list = read_names_list(Cursor::new(NAMES_LIST)).context("Reading NamesList.txt")?;
+    for (c, annotations) in list.annotations {
+        for alias in annotations.aliases {
+            names.insert_phrases(vec![alias], c);
+        }
+    }
+    Ok(())
+}
+
+const PREAMBLE: &str = r#"/// Generated with `make names`
+#[derive(Clone, Debug)]
+pub struct Annotations {
+    pub c: char,
+    pub aliases: &'static [&'static str],
+    pub notes: &'static [&'static str],
+    pub see_also: &'static [char],
+}
+
+"#;
+
+pub fn write_names_list_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let list = read_names_list(Cursor::new(NAMES_LIST)).context("Reading NamesList.txt")?;
+
+    let filename = output.join("names_list.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    write!(&mut out, "{}", PREAMBLE)?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static ANNOTATIONS: &[Annotations; {}] = &[",
+        list.annotations.len()
+    )?;
+    for (c, annotations) in list.annotations {
+        writeln!(
+            &mut out,
+            "    Annotations{{c:{:?}, aliases:&{:?}, notes:&{:?}, see_also:&{:?}}},",
+            c, annotations.aliases, annotations.notes, annotations.see_also
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static SUBHEADERS: &[(char, char, &str); {}] = &[",
+        list.subheaders.len()
+    )?;
+    for subheader in list.subheaders {
+        writeln!(
+            &mut out,
+            "    ({:?}, {:?}, {:?}),",
+            subheader.start, subheader.end, subheader.name
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
@@ -0,0 +1,404 @@
+//! Generate the tables that chars needs to normalize strings:
+//! canonical and compatibility decompositions and canonical combining
+//! classes (taken from UnicodeData.txt), the composition exclusions
+//! from CompositionExclusions.txt, and the quick check properties
+//! derived from them.
+use std::char;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::unicode;
+
+const COMPOSITION_EXCLUSIONS: &[u8] = include_bytes!("../data/unicode/CompositionExclusions.txt");
+
+#[derive(Debug, Default, PartialEq)]
+struct NormalizationData {
+    /// Canonical decomposition mappings, exactly as in UnicodeData.txt.
+    canonical: BTreeMap<char, Vec<char>>,
+    /// Compatibility decomposition mappings (the ones with a `<tag>`).
+    compatibility: BTreeMap<char, Vec<char>>,
+    /// All non-zero canonical combining classes.
+    combining_classes: BTreeMap<char, u8>,
+}
+
+fn parse_codepoint(hex: &str) -> Result<char> {
+    let cp = u32::from_str_radix(hex, 16)
+        .with_context(|| format!("Could not parse {} as base-16 integer", hex))?;
+    char::from_u32(cp).with_context(|| format!("{:?} is not a unicode scalar value", hex))
+}
+
+fn process_line(data: &mut NormalizationData, line: &str) -> Result<()> {
+    if line.starts_with('#') || line.trim_start() == "" {
+        return Ok(());
+    }
+    let fields: Vec<&str> = line.splitn(15, ';').collect();
+    let cp = u32::from_str_radix(fields[0], 16)
+        .with_context(|| format!("Could not parse {} as base-16 integer", fields[0]))?;
+    let ch = match char::from_u32(cp) {
+        Some(ch) => ch,
+        // Surrogates have neither decompositions nor combining classes.
+        None => return Ok(()),
+    };
+    let ccc: u8 = fields[3]
+        .parse()
+        .with_context(|| format!("Could not parse combining class of {:?}", ch))?;
+    if ccc != 0 {
+        data.combining_classes.insert(ch, ccc);
+    }
+    let decomposition = fields[5];
+    if decomposition.is_empty() {
+        return Ok(());
+    }
+    let is_compatibility = decomposition.starts_with('<');
+    let mapping = decomposition
+        .split_whitespace()
+        .filter(|part| !part.starts_with('<'))
+        .map(parse_codepoint)
+        .collect::<Result<Vec<char>>>()?;
+    if is_compatibility {
+        data.compatibility.insert(ch, mapping);
+    } else {
+        data.canonical.insert(ch, mapping);
+    }
+    Ok(())
+}
+
+fn read_normalization_data(reader: impl BufRead) -> Result<NormalizationData> {
+    let mut data = NormalizationData::default();
+    for line in reader.lines() {
+        process_line(&mut data, line?.as_str())?;
+    }
+    Ok(data)
+}
+
+/// Decomposes a Hangul syllable into its conjoining jamo, following
+/// section 3.12 of the Unicode standard.
+fn decompose_hangul(ch: char, out: &mut String) -> bool {
+    const S_BASE: u32 = 0xAC00;
+    const N_COUNT: u32 = 21 * 28;
+    const T_COUNT: u32 = 28;
+    let s_index = (ch as u32).wrapping_sub(S_BASE);
+    if s_index >= 19 * N_COUNT {
+        return false;
+    }
+    out.extend(char::from_u32(0x1100 + s_index / N_COUNT));
+    out.extend(char::from_u32(0x1161 + (s_index % N_COUNT) / T_COUNT));
+    let t = 0x11A7 + s_index % T_COUNT;
+    if t != 0x11A7 {
+        out.extend(char::from_u32(t));
+    }
+    true
+}
+
+/// Applies decomposition mappings recursively, so that the generated
+/// tables map each character to its full decomposition. Compatibility
+/// mappings are only used if `compatibility` is given.
+fn decompose_fully(
+    canonical: &BTreeMap<char, Vec<char>>,
+    compatibility: Option<&BTreeMap<char, Vec<char>>>,
+    ch: char,
+    out: &mut String,
+) {
+    let mapping = canonical
+        .get(&ch)
+        .or_else(|| compatibility.and_then(|compat| compat.get(&ch)));
+    match mapping {
+        Some(mapping) => {
+            for &part in mapping {
+                decompose_fully(canonical, compatibility, part, out);
+            }
+        }
+        None => {
+            if !decompose_hangul(ch, out) {
+                out.push(ch);
+            }
+        }
+    }
+}
+
+fn read_composition_exclusions(reader: impl BufRead) -> Result<BTreeSet<char>> {
+    let mut exclusions = BTreeSet::new();
+    for line in reader.lines() {
+        let line = line?;
+        let data = line.split('#').next().unwrap_or("").trim();
+        if data.is_empty() {
+            continue;
+        }
+        let mut range = data.split("..");
+        let start = parse_codepoint(range.next().unwrap_or(data))?;
+        let end = match range.next() {
+            Some(end) => parse_codepoint(end)?,
+            None => start,
+        };
+        exclusions.extend(start..=end);
+    }
+    Ok(exclusions)
+}
+
+#[test]
+fn test_composition_exclusions() {
+    let exclusions = read_composition_exclusions(Cursor::new(
+        "# CompositionExclusions-15.0.0.txt\n\n0958    #  DEVANAGARI LETTER QA\n2ADC    #  FORKING\n",
+    ))
+    .unwrap();
+    assert_eq!(
+        exclusions.into_iter().collect::<Vec<char>>(),
+        vec!['\u{958}', '\u{2adc}']
+    );
+}
+
+/// Why a character never appears in NFC output, for characters with
+/// the Full_Composition_Exclusion property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Exclusion {
+    /// Listed in CompositionExclusions.txt (the Composition_Exclusion
+    /// property).
+    Listed,
+    /// Decomposes canonically to a single other character.
+    Singleton,
+    /// Is, or decomposes to something that starts with, a non-starter.
+    NonStarter,
+}
+
+impl NormalizationData {
+    fn full_composition_exclusions(&self, listed: &BTreeSet<char>) -> BTreeMap<char, Exclusion> {
+        let mut exclusions = BTreeMap::new();
+        for (&ch, mapping) in self.canonical.iter() {
+            let exclusion = if listed.contains(&ch) {
+                Exclusion::Listed
+            } else if mapping.len() == 1 {
+                Exclusion::Singleton
+            } else if self.combining_classes.contains_key(&ch)
+                || self.combining_classes.contains_key(&mapping[0])
+            {
+                Exclusion::NonStarter
+            } else {
+                continue;
+            };
+            exclusions.insert(ch, exclusion);
+        }
+        exclusions
+    }
+}
+
+/// NFC_Quick_Check and NFKC_Quick_Check property values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum QuickCheck {
+    Yes,
+    No,
+    Maybe,
+}
+
+// Hangul jamo that compose with a preceding jamo or syllable:
+const HANGUL_V_JAMO: std::ops::RangeInclusive<char> = '\u{1161}'..='\u{1175}';
+const HANGUL_T_JAMO: std::ops::RangeInclusive<char> = '\u{11a8}'..='\u{11c2}';
+
+#[test]
+fn test_decomposition() {
+    let mut data = NormalizationData::default();
+    for line in &[
+        "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;",
+        "0302;COMBINING CIRCUMFLEX ACCENT;Mn;230;NSM;;;;;N;NON-SPACING CIRCUMFLEX;;;;",
+        "0303;COMBINING TILDE;Mn;230;NSM;;;;;N;NON-SPACING TILDE;;;;",
+        "00C2;LATIN CAPITAL LETTER A WITH CIRCUMFLEX;Lu;0;L;0041 0302;;;;N;LATIN CAPITAL LETTER A CIRCUMFLEX;;;00E2;",
+        "1EAA;LATIN CAPITAL LETTER A WITH CIRCUMFLEX AND TILDE;Lu;0;L;00C2 0303;;;;N;;;;1EAB;",
+        "00BD;VULGAR FRACTION ONE HALF;No;0;ON;<fraction> 0031 2044 0032;;;1/2;N;FRACTION ONE HALF;;;;",
+    ] {
+        process_line(&mut data, line).unwrap();
+    }
+    assert_eq!(data.combining_classes.get(&'\u{302}'), Some(&230));
+    assert_eq!(data.combining_classes.get(&'A'), None);
+    // Compatibility decompositions aren't canonical:
+    assert_eq!(data.canonical.get(&'\u{bd}'), None);
+
+    let mut decomposed = String::new();
+    decompose_fully(&data.canonical, None, '\u{1eaa}', &mut decomposed);
+    assert_eq!(decomposed, "A\u{302}\u{303}");
+
+    let mut decomposed = String::new();
+    decompose_fully(
+        &data.canonical,
+        Some(&data.compatibility),
+        '\u{bd}',
+        &mut decomposed,
+    );
+    assert_eq!(decomposed, "1\u{2044}2");
+
+    // Hangul syllables in mappings decompose into jamo:
+    process_line(
+        &mut data,
+        "320E;PARENTHESIZED HANGUL KIYEOK A;So;0;L;<compat> 0028 AC00 0029;;;;N;PARENTHESIZED HANGUL GA;;;;",
+    )
+    .unwrap();
+    let mut decomposed = String::new();
+    decompose_fully(
+        &data.canonical,
+        Some(&data.compatibility),
+        '\u{320e}',
+        &mut decomposed,
+    );
+    assert_eq!(decomposed, "(\u{1100}\u{1161})");
+
+    let exclusions = data.full_composition_exclusions(&BTreeSet::new());
+    assert_eq!(exclusions.get(&'\u{c2}'), None);
+}
+
+const PREAMBLE: &str = r#"/// Generated with `make names`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Exclusion {
+    Listed,
+    Singleton,
+    NonStarter,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuickCheck {
+    Yes,
+    No,
+    Maybe,
+}
+
+"#;
+
+pub fn write_normalization_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let data = read_normalization_data(unicode::unicode_data())
+        .context("Reading decompositions from unicode data")?;
+    let listed = read_composition_exclusions(Cursor::new(COMPOSITION_EXCLUSIONS))
+        .context("Reading composition exclusions")?;
+    let exclusions = data.full_composition_exclusions(&listed);
+
+    let mut canonical: BTreeMap<char, String> = BTreeMap::new();
+    for &ch in data.canonical.keys() {
+        let mut decomposed = String::new();
+        decompose_fully(&data.canonical, None, ch, &mut decomposed);
+        canonical.insert(ch, decomposed);
+    }
+    // Only keep compatibility decompositions that differ from the
+    // canonical ones:
+    let mut compatibility: BTreeMap<char, String> = BTreeMap::new();
+    for &ch in data.canonical.keys().chain(data.compatibility.keys()) {
+        let mut decomposed = String::new();
+        decompose_fully(
+            &data.canonical,
+            Some(&data.compatibility),
+            ch,
+            &mut decomposed,
+        );
+        if canonical.get(&ch) != Some(&decomposed) {
+            compatibility.insert(ch, decomposed);
+        }
+    }
+
+    // Primary composites are canonical pairs that aren't excluded:
+    let mut compositions: BTreeMap<(char, char), char> = BTreeMap::new();
+    for (&ch, mapping) in data.canonical.iter() {
+        if mapping.len() == 2 && !exclusions.contains_key(&ch) {
+            compositions.insert((mapping[0], mapping[1]), ch);
+        }
+    }
+
+    let combining_seconds: BTreeSet<char> = compositions
+        .keys()
+        .map(|&(_, second)| second)
+        .chain(HANGUL_V_JAMO)
+        .chain(HANGUL_T_JAMO)
+        .collect();
+    let mut quick_checks: BTreeMap<char, (QuickCheck, QuickCheck)> = BTreeMap::new();
+    for &ch in exclusions
+        .keys()
+        .chain(compatibility.keys())
+        .chain(combining_seconds.iter())
+    {
+        let check = |compat: bool| {
+            if exclusions.contains_key(&ch) || (compat && compatibility.contains_key(&ch)) {
+                QuickCheck::No
+            } else if combining_seconds.contains(&ch) {
+                QuickCheck::Maybe
+            } else {
+                QuickCheck::Yes
+            }
+        };
+        quick_checks.insert(ch, (check(false), check(true)));
+    }
+
+    let filename = output.join("normalization.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    write!(&mut out, "{}", PREAMBLE)?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CANONICAL_DECOMPOSITIONS: &[(char, &str); {}] = &[",
+        canonical.len()
+    )?;
+    for (ch, decomposed) in canonical {
+        writeln!(&mut out, "    ({:?}, {:?}),", ch, decomposed)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static COMPATIBILITY_DECOMPOSITIONS: &[(char, &str); {}] = &[",
+        compatibility.len()
+    )?;
+    for (ch, decomposed) in compatibility {
+        writeln!(&mut out, "    ({:?}, {:?}),", ch, decomposed)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static COMBINING_CLASSES: &[(char, u8); {}] = &[",
+        data.combining_classes.len()
+    )?;
+    for (ch, ccc) in data.combining_classes {
+        writeln!(&mut out, "    ({:?}, {}),", ch, ccc)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static COMPOSITIONS: &[((char, char), char); {}] = &[",
+        compositions.len()
+    )?;
+    for (pair, composite) in compositions {
+        writeln!(&mut out, "    ({:?}, {:?}),", pair, composite)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static COMPOSITION_EXCLUSIONS: &[(char, Exclusion); {}] = &[",
+        exclusions.len()
+    )?;
+    for (ch, exclusion) in exclusions {
+        writeln!(&mut out, "    ({:?}, Exclusion::{:?}),", ch, exclusion)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static QUICK_CHECKS: &[(char, QuickCheck, QuickCheck); {}] = &[",
+        quick_checks.len()
+    )?;
+    for (ch, (nfc, nfkc)) in quick_checks {
+        writeln!(
+            &mut out,
+            "    ({:?}, QuickCheck::{:?}, QuickCheck::{:?}),",
+            ch, nfc, nfkc
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}
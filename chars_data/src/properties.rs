@@ -0,0 +1,302 @@
+//! Generate range tables for enumerated character properties that the
+//! UCD distributes as `<range>;<value>` files, like EastAsianWidth.txt,
+//! LineBreak.txt and DerivedCoreProperties.txt:
+//!
+//!```text
+//!0000..001F;N     # Cc    [32] <control-0000>..<control-001F>
+//!0020;Na          # Zs         SPACE
+//!```
+use std::char;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::unicode;
+
+const EAST_ASIAN_WIDTH: &[u8] = include_bytes!("../data/unicode/EastAsianWidth.txt");
+const LINE_BREAK: &[u8] = include_bytes!("../data/unicode/LineBreak.txt");
+const DERIVED_CORE_PROPERTIES: &[u8] = include_bytes!("../data/unicode/DerivedCoreProperties.txt");
+
+/// A range of code points that share a property value.
+#[derive(Debug, Clone, PartialEq)]
+struct PropertyRange {
+    start: u32,
+    end: u32,
+    value: String,
+}
+
+fn process_line(line: &str) -> Result<Option<PropertyRange>> {
+    let data = line.split('#').next().unwrap_or("");
+    if data.trim().is_empty() {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = data.split(';').map(str::trim).collect();
+    if fields.len() < 2 {
+        bail!("Not enough fields in property line {:?}", line);
+    }
+    let mut range = fields[0].split("..");
+    let parse = |hex: &str| {
+        u32::from_str_radix(hex, 16)
+            .with_context(|| format!("Could not parse {} as base-16 integer", hex))
+    };
+    let start = parse(range.next().unwrap_or(fields[0]))?;
+    let end = match range.next() {
+        Some(end) => parse(end)?,
+        None => start,
+    };
+    Ok(Some(PropertyRange {
+        start,
+        end,
+        value: fields[1].to_string(),
+    }))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(process_line("# EastAsianWidth-15.0.0.txt").unwrap(), None);
+    assert_eq!(
+        process_line("0000..001F;N     # Cc    [32] <control-0000>..<control-001F>").unwrap(),
+        Some(PropertyRange {
+            start: 0,
+            end: 0x1f,
+            value: "N".to_string()
+        })
+    );
+    assert_eq!(
+        process_line("00A1          ; A  # Po         INVERTED EXCLAMATION MARK").unwrap(),
+        Some(PropertyRange {
+            start: 0xa1,
+            end: 0xa1,
+            value: "A".to_string()
+        })
+    );
+    assert!(process_line("00A1").is_err());
+}
+
+/// Parses the default values that a property file gives for the code
+/// points it doesn't list:
+///
+///```text
+///# @missing: 0000..10FFFF; N
+///```
+fn process_missing_line(line: &str) -> Result<Option<PropertyRange>> {
+    match line.strip_prefix("# @missing:") {
+        Some(data) => process_line(data),
+        None => Ok(None),
+    }
+}
+
+#[test]
+fn test_process_missing_line() {
+    assert_eq!(
+        process_missing_line("# @missing: 3400..4DBF; W").unwrap(),
+        Some(PropertyRange {
+            start: 0x3400,
+            end: 0x4dbf,
+            value: "W".to_string()
+        })
+    );
+    assert_eq!(process_missing_line("# comment").unwrap(), None);
+    assert_eq!(process_missing_line("0020;Na").unwrap(), None);
+}
+
+/// Reads the default values from a property file, in the order they
+/// are listed: later ones override earlier ones.
+fn read_missing_ranges(reader: impl BufRead) -> Result<Vec<PropertyRange>> {
+    let mut ranges: Vec<PropertyRange> = vec![];
+    for line in reader.lines() {
+        ranges.extend(process_missing_line(line?.as_str())?);
+    }
+    Ok(ranges)
+}
+
+/// Reads all ranges from a property file, as they are listed.
+fn read_property_ranges(reader: impl BufRead) -> Result<Vec<PropertyRange>> {
+    let mut ranges: Vec<PropertyRange> = vec![];
+    for line in reader.lines() {
+        if let Some(range) = process_line(line?.as_str())? {
+            ranges.push(range);
+        }
+    }
+    Ok(ranges)
+}
+
+/// Reads all ranges from a property file, merging adjacent ranges
+/// with the same value and leaving out surrogates.
+fn read_ranges(reader: impl BufRead) -> Result<Vec<PropertyRange>> {
+    Ok(merge_ranges(read_property_ranges(reader)?))
+}
+
+/// Reads the General_Category of each code point from UnicodeData.txt,
+/// where large ranges are given by their first and last code point.
+fn read_general_categories(reader: impl BufRead) -> Result<Vec<PropertyRange>> {
+    let mut ranges: Vec<PropertyRange> = vec![];
+    let mut first: Option<u32> = None;
+    for line in reader.lines() {
+        let line = line?;
+        let fields: Vec<&str> = line.splitn(4, ';').collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        let cp = u32::from_str_radix(fields[0], 16)
+            .with_context(|| format!("Could not parse {} as base-16 integer", fields[0]))?;
+        if fields[1].ends_with(", First>") {
+            first = Some(cp);
+            continue;
+        }
+        let start = if fields[1].ends_with(", Last>") {
+            first
+                .take()
+                .with_context(|| format!("Range end without a start: {:?}", line))?
+        } else {
+            cp
+        };
+        ranges.push(PropertyRange {
+            start,
+            end: cp,
+            value: fields[2].to_string(),
+        });
+    }
+    Ok(merge_ranges(ranges))
+}
+
+#[test]
+fn test_read_general_categories() {
+    let ranges = read_general_categories(Cursor::new(
+        "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n\
+         0042;LATIN CAPITAL LETTER B;Lu;0;L;;;;;N;;;;0062;\n\
+         AC00;<Hangul Syllable, First>;Lo;0;L;;;;;N;;;;;\n\
+         D7A3;<Hangul Syllable, Last>;Lo;0;L;;;;;N;;;;;\n",
+    ))
+    .unwrap();
+    assert_eq!(
+        ranges,
+        vec![
+            PropertyRange {
+                start: 0x41,
+                end: 0x42,
+                value: "Lu".to_string()
+            },
+            PropertyRange {
+                start: 0xAC00,
+                end: 0xD7A3,
+                value: "Lo".to_string()
+            },
+        ]
+    );
+}
+
+/// Sorts ranges, merging adjacent ones with the same value and leaving
+/// out surrogates.
+fn merge_ranges(mut ranges: Vec<PropertyRange>) -> Vec<PropertyRange> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<PropertyRange> = vec![];
+    for mut range in ranges {
+        if range.start >= 0xD800 && range.end <= 0xDFFF {
+            continue;
+        }
+        if range.start < 0xD800 && range.end >= 0xD800 {
+            merged.push(PropertyRange {
+                start: range.start,
+                end: 0xD7FF,
+                value: range.value.clone(),
+            });
+            range.start = 0xE000;
+        }
+        match merged.last_mut() {
+            Some(last) if last.end + 1 == range.start && last.value == range.value => {
+                last.end = range.end;
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[test]
+fn test_read_ranges() {
+    let ranges = read_ranges(Cursor::new(
+        "0021..0023;Na\n0024;Na\n0026;Na\nD800..DFFF;N\nE000..F8FF;A\n",
+    ))
+    .unwrap();
+    assert_eq!(
+        ranges,
+        vec![
+            PropertyRange {
+                start: 0x21,
+                end: 0x24,
+                value: "Na".to_string()
+            },
+            PropertyRange {
+                start: 0x26,
+                end: 0x26,
+                value: "Na".to_string()
+            },
+            PropertyRange {
+                start: 0xE000,
+                end: 0xF8FF,
+                value: "A".to_string()
+            },
+        ]
+    );
+}
+
+fn write_ranges(out: &mut impl Write, name: &str, ranges: &[PropertyRange]) -> Result<()> {
+    writeln!(out, "#[rustfmt::skip]")?;
+    writeln!(
+        out,
+        "pub static {}: &[(char, char, &str); {}] = &[",
+        name,
+        ranges.len()
+    )?;
+    for range in ranges {
+        let start = char::from_u32(range.start)
+            .with_context(|| format!("{:x} is not a unicode scalar value", range.start))?;
+        let end = char::from_u32(range.end)
+            .with_context(|| format!("{:x} is not a unicode scalar value", range.end))?;
+        writeln!(out, "    ({:?}, {:?}, {:?}),", start, end, range.value)?;
+    }
+    writeln!(out, "];")?;
+    Ok(())
+}
+
+pub fn write_property_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let east_asian_width =
+        read_ranges(Cursor::new(EAST_ASIAN_WIDTH)).context("Reading EastAsianWidth.txt")?;
+    let line_break = read_ranges(Cursor::new(LINE_BREAK)).context("Reading LineBreak.txt")?;
+    let east_asian_width_missing = read_missing_ranges(Cursor::new(EAST_ASIAN_WIDTH))
+        .context("Reading EastAsianWidth.txt defaults")?;
+    let line_break_missing =
+        read_missing_ranges(Cursor::new(LINE_BREAK)).context("Reading LineBreak.txt defaults")?;
+    let general_category =
+        read_general_categories(unicode::unicode_data()).context("Reading UnicodeData.txt")?;
+    // DerivedCoreProperties.txt lists several overlapping binary
+    // properties, so pick out the one we need before merging:
+    let default_ignorable = merge_ranges(
+        read_property_ranges(Cursor::new(DERIVED_CORE_PROPERTIES))
+            .context("Reading DerivedCoreProperties.txt")?
+            .into_iter()
+            .filter(|range| range.value == "Default_Ignorable_Code_Point")
+            .collect(),
+    );
+
+    let filename = output.join("properties.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut out, "/// Generated with `make names`")?;
+    write_ranges(&mut out, "EAST_ASIAN_WIDTH", &east_asian_width)?;
+    write_ranges(&mut out, "LINE_BREAK", &line_break)?;
+    write_ranges(
+        &mut out,
+        "EAST_ASIAN_WIDTH_MISSING",
+        &east_asian_width_missing,
+    )?;
+    write_ranges(&mut out, "LINE_BREAK_MISSING", &line_break_missing)?;
+    write_ranges(&mut out, "GENERAL_CATEGORY", &general_category)?;
+    write_ranges(&mut out, "DEFAULT_IGNORABLE", &default_ignorable)?;
+    Ok(())
+}
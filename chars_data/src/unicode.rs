@@ -186,8 +186,43 @@ fn test_processing() {
     }
 }
 
+/// Returns the Unicode 1.0 name (field 10) of a UnicodeData.txt line,
+/// if it has one.
+fn process_old_name_line(line: &str) -> Result<Option<(char, String)>> {
+    if line.starts_with('#') || line.trim_start() == "" {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = line.splitn(15, ';').collect();
+    let cp = u32::from_str_radix(fields[0], 16)
+        .with_context(|| format!("Could not parse {} as base-16 integer", fields[0]))?;
+    match (char::from_u32(cp), fields.get(10)) {
+        (Some(_), Some(&"")) | (Some(_), None) | (None, _) => Ok(None),
+        (Some(ch), Some(&name)) => Ok(Some((ch, name.to_owned()))),
+    }
+}
+
 #[test]
-fn test_old_names() {}
+fn test_old_names() {
+    assert_eq!(
+        Some(('\u{03bb}', "GREEK SMALL LETTER LAMBDA".to_owned())),
+        process_old_name_line(
+            "03BB;GREEK SMALL LETTER LAMDA;Ll;0;L;;;;;N;GREEK SMALL LETTER LAMBDA;;039B;;039B"
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        Some(('\n', "LINE FEED (LF)".to_owned())),
+        process_old_name_line("000A;<control>;Cc;0;B;;;;;N;LINE FEED (LF);;;;").unwrap()
+    );
+    assert_eq!(
+        None,
+        process_old_name_line(
+            "0214;LATIN CAPITAL LETTER U WITH DOUBLE GRAVE;Lu;0;L;0055 030F;;;;N;;;;0215;"
+        )
+        .unwrap()
+    );
+    assert_eq!(None, process_old_name_line("# comment").unwrap());
+}
 
 pub fn read_names(names: &mut fst_generator::Names, reader: impl BufRead) -> Result<()> {
     let mut lines = reader.lines();
@@ -231,6 +266,112 @@ pub fn read_names(names: &mut fst_generator::Names, reader: impl BufRead) -> Res
     Ok(())
 }
 
+#[derive(PartialEq, Debug)]
+struct NameAlias {
+    ch: char,
+    alias: String,
+    alias_type: String,
+}
+
+fn process_alias_line(line: &str) -> Result<Option<NameAlias>> {
+    if line.starts_with('#') || line.trim_start() == "" {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = line.split(';').collect();
+    if fields.len() != 3 {
+        bail!("Expected three fields in name alias line {:?}", line);
+    }
+    let cp = u32::from_str_radix(fields[0], 16)
+        .with_context(|| format!("Could not parse {} as base-16 integer", fields[0]))?;
+    let ch = char::from_u32(cp).ok_or_else(|| anyhow!("{:?} is not a character", cp))?;
+    Ok(Some(NameAlias {
+        ch,
+        alias: fields[1].to_owned(),
+        alias_type: fields[2].to_owned(),
+    }))
+}
+
+#[test]
+fn test_alias_processing() {
+    assert_eq!(
+        None,
+        process_alias_line("# NameAliases-15.0.0.txt").unwrap()
+    );
+    assert_eq!(None, process_alias_line("").unwrap());
+    assert_eq!(
+        Some(NameAlias {
+            ch: '\u{200D}',
+            alias: "ZWJ".to_owned(),
+            alias_type: "abbreviation".to_owned()
+        }),
+        process_alias_line("200D;ZWJ;abbreviation").unwrap()
+    );
+    assert!(process_alias_line("200D;ZWJ").is_err());
+}
+
+/// Writes the table of name aliases, along with their type
+/// (correction, control, alternate, figment or abbreviation), in
+/// code point order.
+pub fn write_alias_data(reader: impl BufRead, output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut aliases: Vec<NameAlias> = vec![];
+    for line in reader.lines() {
+        if let Some(alias) = process_alias_line(line?.as_str())? {
+            aliases.push(alias);
+        }
+    }
+    aliases.sort_by_key(|alias| alias.ch);
+
+    let filename = output.join("aliases.rs");
+    let mut rust_out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut rust_out, "/// Generated with `make names`")?;
+    writeln!(&mut rust_out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut rust_out,
+        "pub static NAME_ALIASES: &[(char, &str, &str); {}] = &[",
+        aliases.len()
+    )?;
+    for alias in aliases {
+        writeln!(
+            &mut rust_out,
+            "    ({:?}, {:?}, {:?}),",
+            alias.ch, alias.alias, alias.alias_type
+        )?;
+    }
+    writeln!(&mut rust_out, "];")?;
+    Ok(())
+}
+
+/// Writes the table of Unicode 1.0 names, for the characters that
+/// have one.
+pub fn write_old_name_data(reader: impl BufRead, output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let mut old_names: Vec<(char, String)> = vec![];
+    for line in reader.lines() {
+        if let Some(old_name) = process_old_name_line(line?.as_str())? {
+            old_names.push(old_name);
+        }
+    }
+    old_names.sort_by_key(|&(ch, _)| ch);
+
+    let filename = output.join("old_names.rs");
+    let mut rust_out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    writeln!(&mut rust_out, "/// Generated with `make names`")?;
+    writeln!(&mut rust_out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut rust_out,
+        "pub static UNICODE_1_NAMES: &[(char, &str); {}] = &[",
+        old_names.len()
+    )?;
+    for (ch, name) in old_names {
+        writeln!(&mut rust_out, "    ({:?}, {:?}),", ch, name)?;
+    }
+    writeln!(&mut rust_out, "];")?;
+    Ok(())
+}
+
 pub fn write_name_data(names: &fst_generator::Names, output: &Path) -> Result<()> {
     create_dir_all(output)?;
     let fst_byte_filename = output.join("name_fst.bin");
@@ -0,0 +1,617 @@
+//! Generate tables from the Unihan database, which has one property
+//! of a CJK ideograph per line:
+//!
+//!```text
+//!U+6F22    kDefinition    Chinese people; the Han dynasty; the Milky Way
+//!U+6F22    kMandarin      hàn
+//!```
+//!
+//! along with the radicals from CJKRadicals.txt that the radical-stroke
+//! indexes refer to.
+use std::char;
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufRead, BufWriter, Cursor, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::fst_generator;
+
+const UNIHAN_READINGS: &[u8] = include_bytes!("../data/unicode/Unihan_Readings.txt");
+const UNIHAN_IRG_SOURCES: &[u8] = include_bytes!("../data/unicode/Unihan_IRGSources.txt");
+const UNIHAN_VARIANTS: &[u8] = include_bytes!("../data/unicode/Unihan_Variants.txt");
+const CJK_RADICALS: &[u8] = include_bytes!("../data/unicode/CJKRadicals.txt");
+
+/// The variant relations from Unihan_Variants.txt that we show.
+const VARIANT_FIELDS: &[&str] = &[
+    "kSimplifiedVariant",
+    "kTraditionalVariant",
+    "kSemanticVariant",
+    "kZVariant",
+    "kCompatibilityVariant",
+];
+
+/// A single property of an ideograph.
+#[derive(Debug, PartialEq)]
+struct UnihanField {
+    c: char,
+    field: String,
+    value: String,
+}
+
+fn process_line(line: &str) -> Result<Option<UnihanField>> {
+    if line.starts_with('#') || line.trim().is_empty() {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = line.splitn(3, '\t').collect();
+    if fields.len() != 3 {
+        bail!("Expected three fields in Unihan line {:?}", line);
+    }
+    let hex = fields[0]
+        .strip_prefix("U+")
+        .with_context(|| format!("Code point {:?} doesn't start with U+", fields[0]))?;
+    let cp = u32::from_str_radix(hex, 16)
+        .with_context(|| format!("Could not parse {} as base-16 integer", hex))?;
+    let c = char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))?;
+    Ok(Some(UnihanField {
+        c,
+        field: fields[1].to_string(),
+        value: fields[2].trim().to_string(),
+    }))
+}
+
+#[test]
+fn test_process_line() {
+    assert_eq!(process_line("# Unihan_Readings.txt").unwrap(), None);
+    assert_eq!(
+        process_line("U+6F22\tkMandarin\thàn").unwrap(),
+        Some(UnihanField {
+            c: '漢',
+            field: "kMandarin".to_string(),
+            value: "hàn".to_string()
+        })
+    );
+    assert!(process_line("6F22\tkMandarin\thàn").is_err());
+    assert!(process_line("U+6F22\tkMandarin").is_err());
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Readings {
+    definition: String,
+    mandarin: String,
+    cantonese: String,
+    japanese_on: String,
+    japanese_kun: String,
+    hangul: String,
+}
+
+fn read_readings(reader: impl BufRead) -> Result<BTreeMap<char, Readings>> {
+    let mut readings: BTreeMap<char, Readings> = BTreeMap::new();
+    for line in reader.lines() {
+        let field = match process_line(line?.as_str())? {
+            Some(field) => field,
+            None => continue,
+        };
+        let entry = readings.entry(field.c).or_default();
+        match field.field.as_str() {
+            "kDefinition" => entry.definition = field.value,
+            "kMandarin" => entry.mandarin = field.value,
+            "kCantonese" => entry.cantonese = field.value,
+            "kJapaneseOn" => entry.japanese_on = field.value,
+            "kJapaneseKun" => entry.japanese_kun = field.value,
+            "kHangul" => entry.hangul = field.value,
+            _ => {}
+        }
+    }
+    Ok(readings)
+}
+
+/// The form of a Kangxi radical that an ideograph is indexed under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum RadicalForm {
+    Traditional,
+    /// The Chinese simplified form, marked with `'`.
+    Simplified,
+    /// A non-Chinese simplified form, marked with `''` since Unicode
+    /// 15.1.
+    NonChineseSimplified,
+}
+
+impl RadicalForm {
+    fn marker(self) -> &'static str {
+        match self {
+            RadicalForm::Traditional => "",
+            RadicalForm::Simplified => "'",
+            RadicalForm::NonChineseSimplified => "''",
+        }
+    }
+}
+
+/// A radical-stroke index of an ideograph: the number of its Kangxi
+/// radical (possibly in simplified form) and the number of strokes
+/// beyond the radical.
+#[derive(Debug, Clone, PartialEq)]
+struct RadicalStroke {
+    radical: u8,
+    form: RadicalForm,
+    strokes: i8,
+}
+
+/// Parses a radical number like `85` or `120'` (the Chinese simplified
+/// form of radical 120); `''` marks a non-Chinese simplified form.
+fn parse_radical(radical: &str) -> Result<(u8, RadicalForm)> {
+    let number = radical.trim_end_matches('\'');
+    let form = match radical.len() - number.len() {
+        0 => RadicalForm::Traditional,
+        1 => RadicalForm::Simplified,
+        2 => RadicalForm::NonChineseSimplified,
+        _ => bail!("Unknown radical form {:?}", radical),
+    };
+    let number = number
+        .parse()
+        .with_context(|| format!("Could not parse radical number {:?}", radical))?;
+    Ok((number, form))
+}
+
+/// Parses a kRSUnicode value, which lists one or more radical-stroke
+/// indexes like `85.11` or `120'.5`.
+fn parse_radical_strokes(value: &str) -> Result<Vec<RadicalStroke>> {
+    value
+        .split_whitespace()
+        .map(|rs| {
+            let mut parts = rs.splitn(2, '.');
+            let (radical, form) = parse_radical(parts.next().unwrap_or(rs))?;
+            let strokes = parts
+                .next()
+                .with_context(|| format!("No stroke count in {:?}", rs))?
+                .parse()
+                .with_context(|| format!("Could not parse stroke count in {:?}", rs))?;
+            Ok(RadicalStroke {
+                radical,
+                form,
+                strokes,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_radical_strokes() {
+    assert_eq!(
+        parse_radical_strokes("85.11").unwrap(),
+        vec![RadicalStroke {
+            radical: 85,
+            form: RadicalForm::Traditional,
+            strokes: 11
+        }]
+    );
+    assert_eq!(
+        parse_radical_strokes("120'.5 120.6").unwrap(),
+        vec![
+            RadicalStroke {
+                radical: 120,
+                form: RadicalForm::Simplified,
+                strokes: 5
+            },
+            RadicalStroke {
+                radical: 120,
+                form: RadicalForm::Traditional,
+                strokes: 6
+            }
+        ]
+    );
+    assert_eq!(
+        parse_radical_strokes("182''.2").unwrap(),
+        vec![RadicalStroke {
+            radical: 182,
+            form: RadicalForm::NonChineseSimplified,
+            strokes: 2
+        }]
+    );
+    assert!(parse_radical_strokes("85").is_err());
+    assert!(parse_radical_strokes("85''''.1").is_err());
+}
+
+/// Returns the terms that an ideograph can be found by with one of its
+/// radical-stroke indexes. The stroke count is only indexed together
+/// with its radical, so that `radical:120 strokes:6` can't match the
+/// radical of one index and the strokes of another.
+fn radical_stroke_terms(rs: &RadicalStroke) -> Vec<String> {
+    let mut radicals = vec![format!("radical:{}", rs.radical)];
+    if rs.form != RadicalForm::Traditional {
+        radicals.push(format!("radical:{}{}", rs.radical, rs.form.marker()));
+    }
+    let mut terms = vec![];
+    for radical in radicals {
+        terms.push(format!("{} strokes:{}", radical, rs.strokes));
+        terms.push(radical);
+    }
+    terms
+}
+
+#[test]
+fn test_radical_stroke_terms() {
+    let rs = RadicalStroke {
+        radical: 120,
+        form: RadicalForm::Simplified,
+        strokes: 5,
+    };
+    assert_eq!(
+        radical_stroke_terms(&rs),
+        vec![
+            "radical:120 strokes:5",
+            "radical:120",
+            "radical:120' strokes:5",
+            "radical:120'"
+        ]
+    );
+}
+
+fn read_radical_strokes(reader: impl BufRead) -> Result<BTreeMap<char, Vec<RadicalStroke>>> {
+    let mut radical_strokes = BTreeMap::new();
+    for line in reader.lines() {
+        match process_line(line?.as_str())? {
+            Some(field) if field.field == "kRSUnicode" => {
+                radical_strokes.insert(field.c, parse_radical_strokes(&field.value)?);
+            }
+            _ => {}
+        }
+    }
+    Ok(radical_strokes)
+}
+
+/// A line from CJKRadicals.txt: a radical number, the character for
+/// the radical (from the Kangxi Radicals or the CJK Radicals Supplement
+/// block), and the unified ideograph that it looks like.
+#[derive(Debug, Clone, PartialEq)]
+struct CjkRadical {
+    number: u8,
+    form: RadicalForm,
+    radical: Option<char>,
+    ideograph: char,
+}
+
+fn process_radical_line(line: &str) -> Result<Option<CjkRadical>> {
+    let data = line.split('#').next().unwrap_or("");
+    if data.trim().is_empty() {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = data.split(';').map(str::trim).collect();
+    if fields.len() != 3 {
+        bail!("Expected three fields in radical line {:?}", line);
+    }
+    let parse_char = |hex: &str| -> Result<char> {
+        let cp = u32::from_str_radix(hex, 16)
+            .with_context(|| format!("Could not parse {} as base-16 integer", hex))?;
+        char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))
+    };
+    let (number, form) = parse_radical(fields[0])?;
+    let radical = match fields[1] {
+        "" => None,
+        hex => Some(parse_char(hex)?),
+    };
+    Ok(Some(CjkRadical {
+        number,
+        form,
+        radical,
+        ideograph: parse_char(fields[2])?,
+    }))
+}
+
+#[test]
+fn test_process_radical_line() {
+    assert_eq!(
+        process_radical_line("# CJKRadicals-15.0.0.txt").unwrap(),
+        None
+    );
+    assert_eq!(
+        process_radical_line("85; 2F54; 6C34").unwrap(),
+        Some(CjkRadical {
+            number: 85,
+            form: RadicalForm::Traditional,
+            radical: Some('\u{2f54}'),
+            ideograph: '水'
+        })
+    );
+    assert_eq!(
+        process_radical_line("120'; 2EE3; 7E9F").unwrap(),
+        Some(CjkRadical {
+            number: 120,
+            form: RadicalForm::Simplified,
+            radical: Some('\u{2ee3}'),
+            ideograph: '纟'
+        })
+    );
+}
+
+/// Parses the list of variants in a Unihan_Variants.txt value, like
+/// `U+5B57<kMatthews,kMeyerWempe U+5B58`, dropping the sources.
+fn parse_variants(value: &str) -> Result<Vec<char>> {
+    value
+        .split_whitespace()
+        .map(|variant| {
+            let cp = variant.split('<').next().unwrap_or(variant);
+            let hex = cp
+                .strip_prefix("U+")
+                .with_context(|| format!("Variant {:?} doesn't start with U+", variant))?;
+            let cp = u32::from_str_radix(hex, 16)
+                .with_context(|| format!("Could not parse {} as base-16 integer", hex))?;
+            char::from_u32(cp).with_context(|| format!("{:?} is not a character", cp))
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_variants() {
+    assert_eq!(parse_variants("U+6C49").unwrap(), vec!['汉']);
+    assert_eq!(
+        parse_variants("U+4E7E U+5E72<kLau,kMatthews").unwrap(),
+        vec!['乾', '干']
+    );
+    assert!(parse_variants("6C49").is_err());
+}
+
+fn read_variants(reader: impl BufRead) -> Result<Vec<(char, String, char)>> {
+    let mut variants = vec![];
+    for line in reader.lines() {
+        let field = match process_line(line?.as_str())? {
+            Some(field) if VARIANT_FIELDS.contains(&field.field.as_str()) => field,
+            _ => continue,
+        };
+        for variant in parse_variants(&field.value)? {
+            variants.push((field.c, field.field.clone(), variant));
+        }
+    }
+    variants.sort_by_key(|&(c, _, _)| c);
+    Ok(variants)
+}
+
+/// Removes the tone marks from a pinyin syllable, so it can be typed
+/// on any keyboard.
+fn toneless(pinyin: &str) -> String {
+    pinyin
+        .chars()
+        .map(|c| match c {
+            'ā' | 'á' | 'ǎ' | 'à' => 'a',
+            'ē' | 'é' | 'ě' | 'è' | 'ê' | 'ế' | 'ề' => 'e',
+            'ī' | 'í' | 'ǐ' | 'ì' => 'i',
+            'ō' | 'ó' | 'ǒ' | 'ò' => 'o',
+            'ū' | 'ú' | 'ǔ' | 'ù' => 'u',
+            'ǖ' | 'ǘ' | 'ǚ' | 'ǜ' => 'ü',
+            'ń' | 'ň' | 'ǹ' => 'n',
+            'ḿ' => 'm',
+            c => c,
+        })
+        .collect()
+}
+
+#[test]
+fn test_toneless() {
+    assert_eq!(toneless("hàn"), "han");
+    assert_eq!(toneless("shuǐ"), "shui");
+    assert_eq!(toneless("lǜ"), "lü");
+}
+
+/// Splits a kDefinition into the phrases that ideographs can be found
+/// by, dropping punctuation.
+fn definition_phrases(definition: &str) -> Vec<String> {
+    definition
+        .split([';', ','])
+        .map(|phrase| {
+            phrase
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || c == '-' || c == '\'' {
+                        c
+                    } else {
+                        ' '
+                    }
+                })
+                .collect::<String>()
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .filter(|phrase| !phrase.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_definition_phrases() {
+    assert_eq!(
+        definition_phrases("Chinese people; the Han dynasty (206 B.C.)"),
+        vec!["Chinese people", "the Han dynasty 206 B C"]
+    );
+}
+
+/// Returns the terms that an ideograph can be found by with its
+/// definition: each phrase and each word, prefixed with `definition:`
+/// to keep them apart from the words of character names.
+fn definition_terms(definition: &str) -> Vec<String> {
+    let mut terms: Vec<String> = vec![];
+    for phrase in definition_phrases(definition) {
+        let phrase = phrase.to_lowercase();
+        for word in phrase.split_whitespace() {
+            terms.push(format!("definition:{}", word));
+            if word.contains('-') {
+                terms.extend(word.split('-').map(|part| format!("definition:{}", part)));
+            }
+        }
+        terms.push(format!("definition:{}", phrase));
+    }
+    terms.retain(|term| term != "definition:");
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
+#[test]
+fn test_definition_terms() {
+    assert_eq!(
+        definition_terms("the Milky Way; well-being"),
+        vec![
+            "definition:being",
+            "definition:milky",
+            "definition:the",
+            "definition:the milky way",
+            "definition:way",
+            "definition:well",
+            "definition:well-being",
+        ]
+    );
+}
+
+/// Makes ideographs findable by the words of their definition as
+/// `definition:water`, and by their Mandarin reading as `pinyin:han`
+/// (with or without tone marks).
+pub fn read_unihan_names(names: &mut fst_generator::Names) -> Result<()> {
+    let readings =
+        read_readings(Cursor::new(UNIHAN_READINGS)).context("Reading Unihan_Readings.txt")?;
+    for (c, readings) in readings {
+        names.insert_terms(definition_terms(&readings.definition), c);
+        for pinyin in readings.mandarin.split_whitespace() {
+            let mut queries = vec![format!("pinyin:{}", pinyin)];
+            let plain = toneless(pinyin);
+            if plain.contains('ü') {
+                // ü is usually typed as v:
+                queries.push(format!("pinyin:{}", plain.replace('ü', "v")));
+            }
+            queries.push(format!("pinyin:{}", plain));
+            names.insert(queries, c);
+        }
+    }
+    let radical_strokes = read_radical_strokes(Cursor::new(UNIHAN_IRG_SOURCES))
+        .context("Reading Unihan_IRGSources.txt")?;
+    for (c, indexes) in radical_strokes {
+        for rs in indexes {
+            names.insert_terms(radical_stroke_terms(&rs), c);
+        }
+    }
+    Ok(())
+}
+
+const PREAMBLE: &str = r#"/// Generated with `make names`
+#[derive(Clone, Debug)]
+pub struct Readings {
+    pub c: char,
+    pub definition: &'static str,
+    pub mandarin: &'static str,
+    pub cantonese: &'static str,
+    pub japanese_on: &'static str,
+    pub japanese_kun: &'static str,
+    pub hangul: &'static str,
+}
+
+/// The form of a Kangxi radical that an ideograph is indexed under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RadicalForm {
+    Traditional,
+    /// The Chinese simplified form, marked with `'`.
+    Simplified,
+    /// A non-Chinese simplified form, marked with `''`.
+    #[allow(dead_code)] // Only in Unihan since Unicode 15.1.
+    NonChineseSimplified,
+}
+
+#[derive(Clone, Debug)]
+pub struct RadicalStroke {
+    pub c: char,
+    pub radical: u8,
+    pub form: RadicalForm,
+    pub strokes: i8,
+}
+
+#[derive(Clone, Debug)]
+pub struct CjkRadical {
+    pub number: u8,
+    pub form: RadicalForm,
+    pub radical: Option<char>,
+    pub ideograph: char,
+}
+
+"#;
+
+pub fn write_unihan_data(output: &Path) -> Result<()> {
+    create_dir_all(output)?;
+    let readings =
+        read_readings(Cursor::new(UNIHAN_READINGS)).context("Reading Unihan_Readings.txt")?;
+
+    let filename = output.join("unihan.rs");
+    let mut out =
+        BufWriter::new(File::create(&filename).context(format!("Creating {:?}", &filename))?);
+    write!(&mut out, "{}", PREAMBLE)?;
+
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static READINGS: &[Readings; {}] = &[",
+        readings.len()
+    )?;
+    for (c, r) in readings {
+        writeln!(
+            &mut out,
+            "    Readings{{c:{:?}, definition:{:?}, mandarin:{:?}, cantonese:{:?}, \
+             japanese_on:{:?}, japanese_kun:{:?}, hangul:{:?}}},",
+            c, r.definition, r.mandarin, r.cantonese, r.japanese_on, r.japanese_kun, r.hangul
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+
+    let radical_strokes = read_radical_strokes(Cursor::new(UNIHAN_IRG_SOURCES))
+        .context("Reading Unihan_IRGSources.txt")?;
+    let radical_strokes: Vec<(char, RadicalStroke)> = radical_strokes
+        .into_iter()
+        .flat_map(|(c, indexes)| indexes.into_iter().map(move |rs| (c, rs)))
+        .collect();
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static RADICAL_STROKES: &[RadicalStroke; {}] = &[",
+        radical_strokes.len()
+    )?;
+    for (c, rs) in radical_strokes {
+        writeln!(
+            &mut out,
+            "    RadicalStroke{{c:{:?}, radical:{}, form:RadicalForm::{:?}, strokes:{}}},",
+            c, rs.radical, rs.form, rs.strokes
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+
+    let variants =
+        read_variants(Cursor::new(UNIHAN_VARIANTS)).context("Reading Unihan_Variants.txt")?;
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static VARIANTS: &[(char, &str, char); {}] = &[",
+        variants.len()
+    )?;
+    for (c, field, variant) in variants {
+        writeln!(&mut out, "    ({:?}, {:?}, {:?}),", c, field, variant)?;
+    }
+    writeln!(&mut out, "];")?;
+
+    let mut radicals: Vec<CjkRadical> = vec![];
+    for line in Cursor::new(CJK_RADICALS).lines() {
+        if let Some(radical) = process_radical_line(line?.as_str())? {
+            radicals.push(radical);
+        }
+    }
+    radicals.sort_by_key(|radical| (radical.number, radical.form));
+    writeln!(&mut out, "#[rustfmt::skip]")?;
+    writeln!(
+        &mut out,
+        "pub static CJK_RADICALS: &[CjkRadical; {}] = &[",
+        radicals.len()
+    )?;
+    for radical in radicals {
+        writeln!(
+            &mut out,
+            "    CjkRadical{{number:{}, form:RadicalForm::{:?}, radical:{:?}, ideograph:{:?}}},",
+            radical.number, radical.form, radical.radical, radical.ideograph
+        )?;
+    }
+    writeln!(&mut out, "];")?;
+    Ok(())
+}